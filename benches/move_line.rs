@@ -0,0 +1,80 @@
+//! Compares the packed [`MoveLine`] with a plain `Vec<Move>`.
+//!
+//! Run with `cargo bench --bench move_line`.
+
+use std::{
+    hint::black_box,
+    mem::{size_of, size_of_val},
+    time::{Duration, Instant},
+};
+
+use universal_chess_interface::chess::{Move, MoveLine, PackedMove, INLINE_MOVE_LINE_CAPACITY};
+
+const ITERATIONS: u32 = 200_000;
+
+/// Measure the average time of one call to `f`.
+fn measure<F, T>(f: F) -> Duration
+where
+    F: Fn() -> T,
+{
+    // Warm up
+    for _ in 0..ITERATIONS / 10 {
+        black_box(f());
+    }
+
+    let start = Instant::now();
+
+    for _ in 0..ITERATIONS {
+        black_box(f());
+    }
+
+    start.elapsed() / ITERATIONS
+}
+
+fn compare(name: &str, moves: &[Move]) {
+    let vec_time = measure(|| {
+        let mut line = Vec::new();
+
+        for &mv in black_box(moves) {
+            line.push(mv);
+        }
+
+        line
+    });
+
+    let line_time = measure(|| {
+        let mut line = MoveLine::new();
+
+        for &mv in black_box(moves) {
+            line.push(mv);
+        }
+
+        line
+    });
+
+    let vec_bytes = size_of::<Vec<Move>>() + size_of_val(moves);
+    let line_bytes = if moves.len() <= INLINE_MOVE_LINE_CAPACITY {
+        size_of::<MoveLine>()
+    } else {
+        size_of::<MoveLine>() + moves.len() * size_of::<PackedMove>()
+    };
+
+    println!("{name} ({} moves)", moves.len());
+    println!("  Vec<Move>: {vec_time:>10.2?} per line, {vec_bytes:>4} bytes");
+    println!("  MoveLine:  {line_time:>10.2?} per line, {line_bytes:>4} bytes");
+}
+
+fn main() {
+    let short: Vec<Move> = "e2e4 e7e5 g1f3 b8c6 f1b5 a7a6 b5a4 g8f6"
+        .parse::<MoveLine>()
+        .unwrap()
+        .into();
+    let long: Vec<Move> = short.iter().copied().cycle().take(40).collect();
+
+    println!("size of Move:       {} bytes", size_of::<Move>());
+    println!("size of PackedMove: {} bytes", size_of::<PackedMove>());
+    println!();
+
+    compare("short pv", &short);
+    compare("long pv", &long);
+}
@@ -0,0 +1,466 @@
+//! Derive macros for [`universal_chess_interface`](https://docs.rs/universal_chess_interface).
+//!
+//! Use them via the `derive` feature of `universal_chess_interface`.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse_macro_input, spanned::Spanned, Data, DeriveInput, Expr, Fields, Ident, Lit, Type, UnOp,
+};
+
+/// Derive `UciOptions` for a struct with named fields.
+///
+/// Every field becomes an engine option, depending on its type:
+/// `bool` becomes a check, integers become a spin, `String` becomes a string
+/// and all other types become a combo and have to implement `ComboValue`.
+/// For enums, `ComboValue` can be derived, but `Display` has to be implemented by hand,
+/// as it defines the names of the values shown to the user.
+///
+/// The options can be configured with the `#[uci(...)]` attribute:
+///
+/// - `name = "Hash"`: the name of the option.
+///   By default, the field name in title case, e.g. `Move Overhead` for `move_overhead`.
+/// - `min = 1`, `max = 1024`: the range of a spin option.
+///   By default, the range of the integer type.
+/// - `default = 16`: the default value.
+///   By default, the value of the field in `Default::default()`.
+/// - `skip`: the field is not an option.
+///
+/// Literal defaults outside of a literal `min` and `max` are rejected at compile time.
+/// The options are built once, on the first call of `set_option`,
+/// so the struct can't be generic.
+#[proc_macro_derive(UciOptions, attributes(uci))]
+pub fn derive_uci_options(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derive `ComboValue` for an enum without fields.
+///
+/// The values are the variants, in the order they are declared.
+/// The enum has to implement `Display`, which defines the names shown to the user.
+#[proc_macro_derive(ComboValue)]
+pub fn derive_combo_value(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand_combo_value(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// The configuration of a field, parsed from the `#[uci(...)]` attribute.
+struct FieldOption {
+    /// The name of the field.
+    ident: Ident,
+
+    /// The type of the field.
+    ty: Type,
+
+    /// The name of the option.
+    name: String,
+
+    /// The minimum value of a spin option.
+    min: Option<Expr>,
+
+    /// The maximum value of a spin option.
+    max: Option<Expr>,
+
+    /// The default value of the option.
+    default: Option<Expr>,
+}
+
+impl FieldOption {
+    /// The expression for the default value of the field.
+    fn default_value(&self) -> TokenStream2 {
+        let ident = &self.ident;
+
+        match &self.default {
+            Some(Expr::Lit(lit)) if matches!(lit.lit, Lit::Str(_)) && is_string(&self.ty) => {
+                quote!(::std::string::String::from(#lit))
+            }
+            Some(default) => quote!(#default),
+            None => quote!(<Self as ::std::default::Default>::default().#ident),
+        }
+    }
+
+    /// The expression for the option type of the field.
+    fn option_type(&self) -> TokenStream2 {
+        let ty = &self.ty;
+        let default = self.default_value();
+        let to_option = |bound: &Option<Expr>| match bound {
+            Some(bound) => quote!(::std::option::Option::Some(#bound)),
+            None => quote!(::std::option::Option::None),
+        };
+        let min = to_option(&self.min);
+        let max = to_option(&self.max);
+
+        quote! {
+            <#ty as ::universal_chess_interface::engine::OptionField>::option_type(
+                &{
+                    let default: #ty = #default;
+                    default
+                },
+                #min,
+                #max,
+            )
+        }
+    }
+}
+
+/// The value of an integer literal like `16` or `-20`, if the expression is one.
+fn int_literal(expr: &Expr) -> Option<i128> {
+    match expr {
+        Expr::Lit(syn::ExprLit {
+            lit: Lit::Int(int), ..
+        }) => int.base10_parse().ok(),
+        Expr::Unary(syn::ExprUnary {
+            op: UnOp::Neg(_),
+            expr,
+            ..
+        }) => int_literal(expr).map(|value| -value),
+        Expr::Group(group) => int_literal(&group.expr),
+        _ => None,
+    }
+}
+
+/// Check that a literal default is in the range of literal bounds.
+fn check_range(option: &FieldOption) -> syn::Result<()> {
+    let bound = |bound: &Option<Expr>| bound.as_ref().and_then(int_literal);
+    let (min, max) = (bound(&option.min), bound(&option.max));
+
+    if let (Some(min), Some(max)) = (min, max) {
+        if min > max {
+            return Err(syn::Error::new(
+                option.max.span(),
+                "`max` must not be less than `min`",
+            ));
+        }
+    }
+
+    let Some(default) = option.default.as_ref().and_then(int_literal) else {
+        return Ok(());
+    };
+
+    if min.is_some_and(|min| default < min) || max.is_some_and(|max| default > max) {
+        return Err(syn::Error::new(
+            option.default.span(),
+            "`default` must be between `min` and `max`",
+        ));
+    }
+
+    Ok(())
+}
+
+/// Determines if the type is `String`.
+fn is_string(ty: &Type) -> bool {
+    let Type::Path(path) = ty else {
+        return false;
+    };
+
+    path.path
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident == "String")
+}
+
+/// Convert a field name to the name of the option, e.g. `move_overhead` to `Move Overhead`.
+fn title_case(field: &str) -> String {
+    field
+        .trim_start_matches("r#")
+        .split('_')
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Parse the options of all fields of the struct.
+fn parse_fields(input: &DeriveInput) -> syn::Result<Vec<FieldOption>> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new(
+            input.span(),
+            "UciOptions can only be derived for structs",
+        ));
+    };
+
+    let Fields::Named(fields) = &data.fields else {
+        return Err(syn::Error::new(
+            data.fields.span(),
+            "UciOptions can only be derived for structs with named fields",
+        ));
+    };
+
+    let mut options = Vec::new();
+
+    for field in &fields.named {
+        let ident = field
+            .ident
+            .clone()
+            .expect("Named fields have an identifier");
+        let mut option = FieldOption {
+            name: title_case(&ident.to_string()),
+            ident,
+            ty: field.ty.clone(),
+            min: None,
+            max: None,
+            default: None,
+        };
+        let mut skip = false;
+
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("uci"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip") {
+                    skip = true;
+                    return Ok(());
+                }
+
+                let value: Expr = meta.value()?.parse()?;
+
+                if meta.path.is_ident("name") {
+                    let Expr::Lit(syn::ExprLit {
+                        lit: Lit::Str(name),
+                        ..
+                    }) = value
+                    else {
+                        return Err(meta.error("the name must be a string literal"));
+                    };
+
+                    option.name = name.value();
+                } else if meta.path.is_ident("min") {
+                    option.min = Some(value);
+                } else if meta.path.is_ident("max") {
+                    option.max = Some(value);
+                } else if meta.path.is_ident("default") {
+                    option.default = Some(value);
+                } else {
+                    return Err(meta.error("expected `name`, `min`, `max`, `default` or `skip`"));
+                }
+
+                Ok(())
+            })?;
+        }
+
+        if !skip {
+            check_range(&option)?;
+            options.push(option);
+        }
+    }
+
+    Ok(options)
+}
+
+/// Generate the implementation of `UciOptions`.
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = parse_fields(&input)?;
+
+    // The options are cached in a static, which can't depend on generic parameters
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            input.generics.span(),
+            "UciOptions can't be derived for generic structs",
+        ));
+    }
+
+    let ident = &input.ident;
+
+    let options = fields.iter().map(|field| {
+        let name = &field.name;
+        let option_type = field.option_type();
+
+        quote! {
+            ::universal_chess_interface::command::engine_to_gui::EngineOption::new(
+                #name,
+                #option_type,
+            )
+        }
+    });
+
+    let setters = fields.iter().enumerate().map(|(index, field)| {
+        let name = &field.name;
+        let field_ident = &field.ident;
+        let ty = &field.ty;
+
+        quote! {
+            if name.trim().eq_ignore_ascii_case(#name) {
+                let parsed = ::universal_chess_interface::engine::OptionValue::parse(
+                    &options[#index].r#type,
+                    value,
+                )
+                .and_then(<#ty as ::universal_chess_interface::engine::OptionField>::from_value);
+
+                return match parsed {
+                    ::std::option::Option::Some(parsed) => {
+                        self.#field_ident = parsed;
+                        ::std::result::Result::Ok(())
+                    }
+                    ::std::option::Option::None => ::std::result::Result::Err(
+                        ::universal_chess_interface::engine::OptionError::InvalidValue {
+                            name: ::std::string::String::from(#name),
+                            value: value.map(::std::string::ToString::to_string),
+                        },
+                    ),
+                };
+            }
+        }
+    });
+
+    Ok(quote! {
+        impl ::universal_chess_interface::engine::UciOptions for #ident {
+            fn options() -> ::std::vec::Vec<
+                ::universal_chess_interface::command::engine_to_gui::EngineOption,
+            > {
+                ::std::vec![#(#options),*]
+            }
+
+            fn set_option(
+                &mut self,
+                name: &str,
+                value: ::std::option::Option<&str>,
+            ) -> ::std::result::Result<(), ::universal_chess_interface::engine::OptionError> {
+                static OPTIONS: ::std::sync::OnceLock<
+                    ::std::vec::Vec<
+                        ::universal_chess_interface::command::engine_to_gui::EngineOption,
+                    >,
+                > = ::std::sync::OnceLock::new();
+                let options = OPTIONS.get_or_init(
+                    <Self as ::universal_chess_interface::engine::UciOptions>::options,
+                );
+
+                #(#setters)*
+
+                ::std::result::Result::Err(
+                    ::universal_chess_interface::engine::OptionError::UnknownName(
+                        ::std::string::String::from(name),
+                    ),
+                )
+            }
+        }
+    })
+}
+
+/// Generate the implementation of `ComboValue`.
+fn expand_combo_value(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Enum(data) = &input.data else {
+        return Err(syn::Error::new(
+            input.span(),
+            "ComboValue can only be derived for enums",
+        ));
+    };
+
+    if let Some(variant) = data
+        .variants
+        .iter()
+        .find(|variant| !matches!(variant.fields, Fields::Unit))
+    {
+        return Err(syn::Error::new(
+            variant.fields.span(),
+            "ComboValue can only be derived for enums without fields",
+        ));
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    let variants = data.variants.iter().map(|variant| &variant.ident);
+
+    Ok(quote! {
+        impl #impl_generics ::universal_chess_interface::engine::ComboValue
+            for #ident #type_generics #where_clause
+        {
+            fn values() -> ::std::vec::Vec<Self> {
+                ::std::vec![#(Self::#variants),*]
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    /// The error message of the derived implementation, if it fails.
+    fn expand_error(input: DeriveInput) -> Option<String> {
+        expand(input).err().map(|error| error.to_string())
+    }
+
+    #[test]
+    fn convert_field_name_to_title_case() {
+        assert_eq!(title_case("move_overhead"), "Move Overhead");
+        assert_eq!(title_case("threads"), "Threads");
+        assert_eq!(title_case("r#type"), "Type");
+        assert_eq!(title_case("_multi__pv"), "Multi Pv");
+    }
+
+    #[test]
+    fn reject_default_out_of_range() {
+        assert_eq!(
+            expand_error(parse_quote! {
+                struct Settings {
+                    #[uci(min = 1, max = 1024, default = 2048)]
+                    hash: u32,
+                }
+            }),
+            Some("`default` must be between `min` and `max`".to_string())
+        );
+        assert_eq!(
+            expand_error(parse_quote! {
+                struct Settings {
+                    #[uci(min = -100, default = -200)]
+                    contempt: i32,
+                }
+            }),
+            Some("`default` must be between `min` and `max`".to_string())
+        );
+        assert_eq!(
+            expand_error(parse_quote! {
+                struct Settings {
+                    #[uci(min = 10, max = 1)]
+                    threads: u32,
+                }
+            }),
+            Some("`max` must not be less than `min`".to_string())
+        );
+        assert_eq!(
+            expand_error(parse_quote! {
+                struct Settings {
+                    #[uci(min = -100, max = 100, default = -20)]
+                    contempt: i32,
+                    #[uci(min = 1, max = MAX_THREADS, default = 1024)]
+                    threads: u32,
+                }
+            }),
+            None
+        );
+    }
+
+    #[test]
+    fn reject_combo_value_for_enum_with_fields() {
+        let error = expand_combo_value(parse_quote! {
+            enum Style {
+                Solid,
+                Risky(u8),
+            }
+        })
+        .err()
+        .map(|error| error.to_string());
+
+        assert_eq!(
+            error,
+            Some("ComboValue can only be derived for enums without fields".to_string())
+        );
+    }
+}
@@ -0,0 +1,150 @@
+use std::fmt::Display;
+
+use universal_chess_interface::{
+    chess::Variant,
+    command::engine_to_gui::{
+        CheckOption, ComboOption, EngineOption, OptionType, SpinOption, StringOption,
+    },
+    engine::{ComboValue, OptionError, UciOptions},
+};
+
+#[derive(Debug, PartialEq, UciOptions)]
+struct Settings {
+    #[uci(name = "Hash", min = 1, max = 1024, default = 16)]
+    hash_mb: u32,
+
+    #[uci(default = -20)]
+    contempt: i16,
+
+    ponder: bool,
+
+    #[uci(name = "UCI_Variant", default = Variant::Standard)]
+    variant: Variant,
+
+    #[uci(name = "SyzygyPath", default = "<empty>")]
+    syzygy_path: String,
+
+    #[uci(skip)]
+    searches: u64,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            hash_mb: 16,
+            contempt: -20,
+            ponder: true,
+            variant: Variant::Standard,
+            syzygy_path: String::new(),
+            searches: 0,
+        }
+    }
+}
+
+#[test]
+fn derive_options() {
+    assert_eq!(
+        Settings::options(),
+        [
+            EngineOption::hash(1, 1024, 16),
+            EngineOption::new(
+                "Contempt",
+                OptionType::Spin(SpinOption::new(i16::MIN.into(), i16::MAX.into(), -20))
+            ),
+            EngineOption::new("Ponder", OptionType::Check(CheckOption { default: true })),
+            EngineOption::variant(&Variant::ALL, Variant::Standard),
+            EngineOption::new(
+                "SyzygyPath",
+                OptionType::String(StringOption {
+                    default: "<empty>".to_string()
+                })
+            ),
+        ]
+    );
+}
+
+#[test]
+fn derive_set_option() {
+    let mut settings = Settings::default();
+
+    settings.set_option("hash", Some("256")).unwrap();
+    settings.set_option("Contempt", Some("-5")).unwrap();
+    settings.set_option("Ponder", Some("false")).unwrap();
+    settings
+        .set_option("UCI_Variant", Some("crazyhouse"))
+        .unwrap();
+    settings.set_option("SyzygyPath", Some("/tb")).unwrap();
+
+    assert_eq!(
+        settings,
+        Settings {
+            hash_mb: 256,
+            contempt: -5,
+            ponder: false,
+            variant: Variant::Crazyhouse,
+            syzygy_path: "/tb".to_string(),
+            searches: 0,
+        }
+    );
+}
+
+#[test]
+fn derive_set_option_validates_values() {
+    let mut settings = Settings::default();
+
+    assert_eq!(
+        settings.set_option("Hash", Some("2048")),
+        Err(OptionError::InvalidValue {
+            name: "Hash".to_string(),
+            value: Some("2048".to_string())
+        })
+    );
+    assert!(settings.set_option("Ponder", Some("maybe")).is_err());
+    assert!(settings.set_option("UCI_Variant", Some("shogi")).is_err());
+    assert_eq!(
+        settings.set_option("Searches", Some("1")),
+        Err(OptionError::UnknownName("Searches".to_string()))
+    );
+    assert_eq!(settings, Settings::default());
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ComboValue)]
+enum Style {
+    Solid,
+    Risky,
+}
+
+impl Display for Style {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Style::Solid => write!(f, "Solid"),
+            Style::Risky => write!(f, "Risky"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, UciOptions)]
+struct StyleSettings {
+    #[uci(default = Style::Solid)]
+    style: Style,
+}
+
+#[test]
+fn derive_combo_value() {
+    let mut settings = StyleSettings {
+        style: Style::Solid,
+    };
+
+    assert_eq!(Style::values(), [Style::Solid, Style::Risky]);
+    assert_eq!(
+        StyleSettings::options(),
+        [EngineOption::new(
+            "Style",
+            OptionType::Combo(ComboOption::new(["Solid", "Risky"], "Solid"))
+        )]
+    );
+
+    settings.set_option("style", Some("risky")).unwrap();
+
+    assert_eq!(settings.style, Style::Risky);
+}
@@ -0,0 +1,86 @@
+use std::{fmt::Display, str::FromStr};
+
+use super::ParseError;
+
+/// The color of a player, denoted `w` or `b` like in FEN.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Color {
+    /// The white player, denoted `w`.
+    White,
+
+    /// The black player, denoted `b`.
+    Black,
+}
+
+impl Color {
+    /// The color of the other player.
+    pub const fn opposite(&self) -> Self {
+        match self {
+            Color::White => Color::Black,
+            Color::Black => Color::White,
+        }
+    }
+}
+
+impl FromStr for Color {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let color = match s {
+            "w" => Color::White,
+            "b" => Color::Black,
+            _ => return Err(ParseError),
+        };
+
+        Ok(color)
+    }
+}
+
+impl Display for Color {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let color = match self {
+            Color::White => "w",
+            Color::Black => "b",
+        };
+
+        write!(f, "{color}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("w", Color::White)]
+    #[case("b", Color::Black)]
+    fn parse_color_ok(#[case] input: &str, #[case] expected: Color) {
+        let actual = input.parse();
+        assert_eq!(actual, Ok(expected));
+    }
+
+    #[rstest]
+    #[case("W")]
+    #[case("white")]
+    #[case("")]
+    fn parse_color_err(#[case] input: &str) {
+        let actual = input.parse::<Color>();
+        assert!(actual.is_err());
+    }
+
+    #[rstest]
+    #[case(Color::White, "w")]
+    #[case(Color::Black, "b")]
+    fn format_color(#[case] input: Color, #[case] expected: String) {
+        let actual = format!("{input}");
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case(Color::White, Color::Black)]
+    #[case(Color::Black, Color::White)]
+    fn opposite_color(#[case] input: Color, #[case] expected: Color) {
+        assert_eq!(input.opposite(), expected);
+    }
+}
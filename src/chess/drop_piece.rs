@@ -0,0 +1,96 @@
+use std::{fmt::Display, str::FromStr};
+
+use super::ParseError;
+
+/// A piece that can be dropped onto the board in drop variants like crazyhouse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DropPiece {
+    /// Dropping a pawn, denoted 'P'.
+    Pawn,
+
+    /// Dropping a knight, denoted 'N'.
+    Knight,
+
+    /// Dropping a bishop, denoted 'B'.
+    Bishop,
+
+    /// Dropping a rook, denoted 'R'.
+    Rook,
+
+    /// Dropping a queen, denoted 'Q'.
+    Queen,
+}
+
+impl FromStr for DropPiece {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.len() != 1 {
+            return Err(ParseError);
+        }
+
+        let piece = match s {
+            "P" => DropPiece::Pawn,
+            "N" => DropPiece::Knight,
+            "B" => DropPiece::Bishop,
+            "R" => DropPiece::Rook,
+            "Q" => DropPiece::Queen,
+            _ => return Err(ParseError),
+        };
+
+        Ok(piece)
+    }
+}
+
+impl Display for DropPiece {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let piece = match self {
+            DropPiece::Pawn => "P",
+            DropPiece::Knight => "N",
+            DropPiece::Bishop => "B",
+            DropPiece::Rook => "R",
+            DropPiece::Queen => "Q",
+        };
+
+        write!(f, "{piece}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("P", DropPiece::Pawn)]
+    #[case("N", DropPiece::Knight)]
+    #[case("B", DropPiece::Bishop)]
+    #[case("R", DropPiece::Rook)]
+    #[case("Q", DropPiece::Queen)]
+    fn parse_drop_piece_ok(#[case] input: &str, #[case] expected: DropPiece) {
+        let actual = input.parse();
+        assert_eq!(actual, Ok(expected));
+    }
+
+    #[rstest]
+    #[case("p")]
+    #[case("n")]
+    #[case("K")]
+    #[case("")]
+    #[case("PP")]
+    fn parse_drop_piece_err(#[case] input: &str) {
+        let actual = input.parse::<DropPiece>();
+        assert!(actual.is_err());
+    }
+
+    #[rstest]
+    #[case(DropPiece::Pawn, "P")]
+    #[case(DropPiece::Knight, "N")]
+    #[case(DropPiece::Bishop, "B")]
+    #[case(DropPiece::Rook, "R")]
+    #[case(DropPiece::Queen, "Q")]
+    fn format_drop_piece(#[case] input: DropPiece, #[case] expected: String) {
+        let actual = format!("{input}");
+        assert_eq!(actual, expected);
+    }
+}
@@ -3,7 +3,7 @@ use std::{fmt::Display, str::FromStr};
 use super::ParseError;
 
 /// A file of the board, denoted from a to h.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum File {
     /// The first file of the board, denoted `a`.
     A,
@@ -30,6 +30,39 @@ pub enum File {
     H,
 }
 
+impl File {
+    /// The zero-based index of the file, starting with File::A as `0`.
+    pub const fn index(&self) -> u8 {
+        match self {
+            File::A => 0,
+            File::B => 1,
+            File::C => 2,
+            File::D => 3,
+            File::E => 4,
+            File::F => 5,
+            File::G => 6,
+            File::H => 7,
+        }
+    }
+
+    /// Get the file with the given zero-based index, if it is in range.
+    pub const fn from_index(index: u8) -> Option<Self> {
+        let file = match index {
+            0 => File::A,
+            1 => File::B,
+            2 => File::C,
+            3 => File::D,
+            4 => File::E,
+            5 => File::F,
+            6 => File::G,
+            7 => File::H,
+            _ => return None,
+        };
+
+        Some(file)
+    }
+}
+
 impl FromStr for File {
     type Err = ParseError;
 
@@ -105,7 +138,7 @@ mod tests {
     #[case("c5")]
     fn parse_file_err(#[case] input: &str) {
         let actual = input.parse::<File>();
-        assert!(matches!(actual, Err(_)));
+        assert!(actual.is_err());
     }
 
     #[rstest]
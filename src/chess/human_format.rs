@@ -0,0 +1,239 @@
+use super::{Color, Evaluation, ParseError, Score};
+
+/// Formats and parses evaluations the way humans write them, e.g. in PGN comments.
+///
+/// Centipawn evaluations are written in pawns with a sign, e.g. `+0.37` or `-1.20`.
+/// Checkmates are written as `#3` if the player can give checkmate in 3 moves
+/// and as `-#4` if the player gets checkmated in 4 moves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HumanFormat {
+    /// The number of decimal places for pawn values.
+    precision: usize,
+
+    /// The side to move, if the evaluations are shown from White's point of view.
+    ///
+    /// If `None`, the evaluations are shown from the point of view of the side to move.
+    side_to_move: Option<Color>,
+}
+
+impl HumanFormat {
+    /// Create a new format with two decimal places, from the point of view of the side to move.
+    pub const fn new() -> Self {
+        Self {
+            precision: 2,
+            side_to_move: None,
+        }
+    }
+
+    /// Set the number of decimal places for pawn values.
+    pub const fn with_precision(mut self, precision: usize) -> Self {
+        self.precision = precision;
+        self
+    }
+
+    /// Show the evaluations from White's point of view, given the side to move.
+    pub const fn from_white_pov(mut self, side_to_move: Color) -> Self {
+        self.side_to_move = Some(side_to_move);
+        self
+    }
+
+    /// Format the evaluation, e.g. as `+0.37`, `#3` or `-#4`.
+    pub fn format(&self, eval: Evaluation) -> String {
+        let eval = match self.side_to_move {
+            Some(side_to_move) => eval.to_white_pov(side_to_move),
+            None => eval,
+        };
+
+        match eval {
+            Evaluation::PlayerCheckmate(moves) => format!("#{moves}"),
+            Evaluation::OpponentCheckmate(moves) => format!("-#{moves}"),
+            Evaluation::Centipawns(cp) => {
+                let pawns = format!(
+                    "{:.precision$}",
+                    f64::from(cp).abs() / 100.0,
+                    precision = self.precision
+                );
+
+                // Don't show a sign if the value is rounded to zero
+                if !pawns.chars().any(|c| matches!(c, '1'..='9')) {
+                    pawns
+                } else if cp > 0 {
+                    format!("+{pawns}")
+                } else {
+                    format!("-{pawns}")
+                }
+            }
+        }
+    }
+
+    /// Format the evaluation of the score, e.g. as `+0.37`, `#3` or `-#4`.
+    pub fn format_score(&self, score: &Score) -> String {
+        self.format(score.eval())
+    }
+
+    /// Parse an evaluation like `+0.37`, `#3` or `-#4`.
+    ///
+    /// If the format is from White's point of view,
+    /// the evaluation is converted back to the point of view of the side to move.
+    pub fn parse(&self, s: &str) -> Result<Evaluation, ParseError> {
+        let (is_negative, unsigned) = match s.as_bytes().first() {
+            Some(b'+') => (false, &s[1..]),
+            Some(b'-') => (true, &s[1..]),
+            _ => (false, s),
+        };
+
+        let eval = if let Some(moves) = unsigned.strip_prefix('#') {
+            if moves.is_empty() || !moves.bytes().all(|c| c.is_ascii_digit()) {
+                return Err(ParseError);
+            }
+
+            let moves = moves.parse::<u16>().map_err(|_| ParseError)?;
+
+            if is_negative {
+                Evaluation::OpponentCheckmate(moves)
+            } else {
+                Evaluation::PlayerCheckmate(moves)
+            }
+        } else {
+            let is_number = unsigned.bytes().any(|c| c.is_ascii_digit())
+                && unsigned.bytes().all(|c| c.is_ascii_digit() || c == b'.')
+                && unsigned.bytes().filter(|&c| c == b'.').count() <= 1;
+
+            if !is_number {
+                return Err(ParseError);
+            }
+
+            let pawns = unsigned.parse::<f64>().map_err(|_| ParseError)?;
+            let cp = (pawns * 100.0).round();
+
+            if cp > f64::from(i32::MAX) {
+                return Err(ParseError);
+            }
+
+            let cp = cp as i32;
+            Evaluation::Centipawns(if is_negative { -cp } else { cp })
+        };
+
+        Ok(match self.side_to_move {
+            // Converting to White's point of view is its own inverse
+            Some(side_to_move) => eval.to_white_pov(side_to_move),
+            None => eval,
+        })
+    }
+}
+
+impl Default for HumanFormat {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(HumanFormat::new(), Evaluation::Centipawns(37), "+0.37")]
+    #[case(HumanFormat::new(), Evaluation::Centipawns(-120), "-1.20")]
+    #[case(HumanFormat::new(), Evaluation::Centipawns(0), "0.00")]
+    #[case(HumanFormat::new().with_precision(1), Evaluation::Centipawns(-4), "0.0")]
+    #[case(HumanFormat::new().with_precision(1), Evaluation::Centipawns(-5), "-0.1")]
+    #[case(HumanFormat::new().with_precision(0), Evaluation::Centipawns(260), "+3")]
+    #[case(HumanFormat::new(), Evaluation::PlayerCheckmate(3), "#3")]
+    #[case(HumanFormat::new(), Evaluation::OpponentCheckmate(4), "-#4")]
+    #[case(
+        HumanFormat::new().from_white_pov(Color::White),
+        Evaluation::Centipawns(37),
+        "+0.37"
+    )]
+    #[case(
+        HumanFormat::new().from_white_pov(Color::Black),
+        Evaluation::Centipawns(37),
+        "-0.37"
+    )]
+    #[case(
+        HumanFormat::new().from_white_pov(Color::Black),
+        Evaluation::PlayerCheckmate(2),
+        "-#2"
+    )]
+    #[case(
+        HumanFormat::new().from_white_pov(Color::Black),
+        Evaluation::OpponentCheckmate(0),
+        "#0"
+    )]
+    #[case(
+        HumanFormat::new().from_white_pov(Color::White),
+        Evaluation::OpponentCheckmate(0),
+        "-#0"
+    )]
+    fn format_human(
+        #[case] format: HumanFormat,
+        #[case] eval: Evaluation,
+        #[case] expected: String,
+    ) {
+        assert_eq!(format.format(eval), expected);
+    }
+
+    #[rstest]
+    #[case(HumanFormat::new(), "+0.37", Evaluation::Centipawns(37))]
+    #[case(HumanFormat::new(), "0.37", Evaluation::Centipawns(37))]
+    #[case(HumanFormat::new(), "-1.2", Evaluation::Centipawns(-120))]
+    #[case(HumanFormat::new(), "3", Evaluation::Centipawns(300))]
+    #[case(HumanFormat::new(), ".5", Evaluation::Centipawns(50))]
+    #[case(HumanFormat::new(), "#3", Evaluation::PlayerCheckmate(3))]
+    #[case(HumanFormat::new(), "+#3", Evaluation::PlayerCheckmate(3))]
+    #[case(HumanFormat::new(), "-#4", Evaluation::OpponentCheckmate(4))]
+    #[case(
+        HumanFormat::new().from_white_pov(Color::Black),
+        "-0.37",
+        Evaluation::Centipawns(37)
+    )]
+    #[case(
+        HumanFormat::new().from_white_pov(Color::Black),
+        "#5",
+        Evaluation::OpponentCheckmate(5)
+    )]
+    #[case(
+        HumanFormat::new().from_white_pov(Color::Black),
+        "#0",
+        Evaluation::OpponentCheckmate(0)
+    )]
+    fn parse_human_ok(
+        #[case] format: HumanFormat,
+        #[case] input: &str,
+        #[case] expected: Evaluation,
+    ) {
+        assert_eq!(format.parse(input), Ok(expected));
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("+")]
+    #[case("#")]
+    #[case("-#")]
+    #[case("#-3")]
+    #[case("#+3")]
+    #[case("##3")]
+    #[case("--1")]
+    #[case("1.2.3")]
+    #[case("1e3")]
+    #[case("inf")]
+    #[case("NaN")]
+    #[case("#1.5")]
+    #[case("99999999999")]
+    #[case("cp 37")]
+    fn parse_human_err(#[case] input: &str) {
+        assert!(HumanFormat::new().parse(input).is_err());
+    }
+
+    #[rstest]
+    #[case(Evaluation::Centipawns(37))]
+    #[case(Evaluation::Centipawns(-1234))]
+    #[case(Evaluation::PlayerCheckmate(1))]
+    #[case(Evaluation::OpponentCheckmate(12))]
+    fn format_and_parse_human(#[case] eval: Evaluation) {
+        let format = HumanFormat::new().from_white_pov(Color::Black);
+        assert_eq!(format.parse(&format.format(eval)), Ok(eval));
+    }
+}
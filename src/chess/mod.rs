@@ -1,20 +1,40 @@
 //! Several general chess constructs needed for UCI, such as moves.
 
+mod color;
+mod drop_piece;
 mod file;
+mod human_format;
 mod r#move;
 mod move_line;
+mod outcome;
+mod packed_move;
+mod piece;
+mod pocket;
+mod position;
 mod promotion_piece;
 mod rank;
 mod score;
 mod square;
+mod variant;
+mod wdl;
 
+pub use color::*;
+pub use drop_piece::*;
 pub use file::*;
+pub use human_format::*;
 pub use move_line::*;
+pub use outcome::*;
+pub use packed_move::*;
+pub use piece::*;
+pub use pocket::*;
+pub use position::*;
 pub use promotion_piece::*;
 pub use r#move::*;
 pub use rank::*;
 pub use score::*;
 pub use square::*;
+pub use variant::*;
+pub use wdl::*;
 
 /// An error occured while parsing the given text.
 #[derive(Debug, PartialEq, Eq)]
@@ -1,50 +1,106 @@
 use std::{fmt::Display, str::FromStr};
 
-use super::{ParseError, PromotionPiece, Square};
+use super::{DropPiece, ParseError, PromotionPiece, Square};
 
 /// A move on the board, in long algebraic notation.
-#[derive(Debug, PartialEq, Eq)]
+///
+/// In drop variants like crazyhouse, a move can also drop a piece onto the board, e.g. `P@e4`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Move {
     /// The source square where the moving piece started.
-    source: Square,
+    ///
+    /// This is `None` if the piece is dropped onto the board.
+    source: Option<Square>,
 
     /// The target square where the moving piece is going to.
     target: Square,
 
     /// The piece that the pawn promoted to, if the move is a promotion.
     promotion: Option<PromotionPiece>,
+
+    /// The piece that is dropped onto the board, if the move is a drop.
+    drop: Option<DropPiece>,
 }
 
 impl Move {
     /// Create a new move without a promotion.
     pub fn new(source: Square, target: Square) -> Self {
         Move {
-            source,
+            source: Some(source),
             target,
             promotion: None,
+            drop: None,
         }
     }
 
     /// Create a new move with a promoting pawn.
     pub fn new_with_promotion(source: Square, target: Square, promotion: PromotionPiece) -> Self {
         Move {
-            source,
+            source: Some(source),
             target,
             promotion: Some(promotion),
+            drop: None,
         }
     }
+
+    /// Create a new move dropping the given piece onto the target square.
+    pub fn new_drop(piece: DropPiece, target: Square) -> Self {
+        Move {
+            source: None,
+            target,
+            promotion: None,
+            drop: Some(piece),
+        }
+    }
+
+    /// The square where the moving piece started, or `None` if the move is a drop.
+    pub fn source(&self) -> Option<Square> {
+        self.source
+    }
+
+    /// The square where the moving or dropped piece is going to.
+    pub fn target(&self) -> Square {
+        self.target
+    }
+
+    /// The piece that the pawn promotes to, if the move is a promotion.
+    pub fn promotion(&self) -> Option<PromotionPiece> {
+        self.promotion
+    }
+
+    /// The piece that is dropped onto the board, if the move is a drop.
+    pub fn drop_piece(&self) -> Option<DropPiece> {
+        self.drop
+    }
+
+    /// Determines if the move drops a piece onto the board.
+    pub fn is_drop(&self) -> bool {
+        self.drop.is_some()
+    }
 }
 
 impl FromStr for Move {
     type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.len() != 4 && s.len() != 5 {
+        if !s.is_ascii() || (s.len() != 4 && s.len() != 5) {
             return Err(ParseError);
         }
 
-        let source = s[(0..2)].parse::<Square>()?;
-        let target = s[(2..4)].parse::<Square>()?;
+        // Drops like `P@e4`
+        if &s[1..2] == "@" {
+            if s.len() != 4 {
+                return Err(ParseError);
+            }
+
+            let piece = s[0..1].parse::<DropPiece>()?;
+            let target = s[2..4].parse::<Square>()?;
+
+            return Ok(Move::new_drop(piece, target));
+        }
+
+        let source = s[0..2].parse::<Square>()?;
+        let target = s[2..4].parse::<Square>()?;
 
         let promotion = if s.len() == 5 {
             Some(s[4..5].parse::<PromotionPiece>()?)
@@ -53,19 +109,28 @@ impl FromStr for Move {
         };
 
         Ok(Move {
-            source,
+            source: Some(source),
             target,
             promotion,
+            drop: None,
         })
     }
 }
 
 impl Display for Move {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(piece) = &self.drop {
+            return write!(f, "{piece}@{}", self.target);
+        }
+
+        let source = self
+            .source
+            .expect("A move without a drop must have a source");
+
         if let Some(promotion) = &self.promotion {
-            write!(f, "{}{}{promotion}", self.source, self.target)
+            write!(f, "{source}{}{promotion}", self.target)
         } else {
-            write!(f, "{}{}", self.source, self.target)
+            write!(f, "{source}{}", self.target)
         }
     }
 }
@@ -97,6 +162,22 @@ mod tests {
             PromotionPiece::Queen
         )
     )]
+    #[case(
+        "b2b1k",
+        Move::new_with_promotion(
+            Square::new(File::B, Rank::Two),
+            Square::new(File::B, Rank::One),
+            PromotionPiece::King
+        )
+    )]
+    #[case(
+        "P@e4",
+        Move::new_drop(DropPiece::Pawn, Square::new(File::E, Rank::Four))
+    )]
+    #[case(
+        "N@f7",
+        Move::new_drop(DropPiece::Knight, Square::new(File::F, Rank::Seven))
+    )]
     fn parse_move_ok(#[case] input: &str, #[case] expected: Move) {
         let actual = input.parse();
         assert_eq!(actual, Ok(expected));
@@ -111,11 +192,16 @@ mod tests {
     #[case("e2E4")]
     #[case("a0a1")]
     #[case("e7e8p")]
-    #[case("e7e8k")]
+    #[case("e7e8K")]
     #[case("e7e8qn")]
+    #[case("p@e4")]
+    #[case("K@e4")]
+    #[case("P@e4q")]
+    #[case("Pe4")]
+    #[case("e2é4")]
     fn parse_move_err(#[case] input: &str) {
         let actual = input.parse::<Move>();
-        assert!(matches!(actual, Err(_)));
+        assert!(actual.is_err());
     }
 
     #[rstest]
@@ -139,6 +225,10 @@ mod tests {
         ),
         "e7e8q"
     )]
+    #[case(
+        Move::new_drop(DropPiece::Queen, Square::new(File::D, Rank::One)),
+        "Q@d1"
+    )]
     fn format_move(#[case] input: Move, #[case] expected: String) {
         let actual = format!("{input}");
         assert_eq!(actual, expected);
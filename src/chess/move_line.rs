@@ -1,52 +1,161 @@
-use std::{fmt::Display, str::FromStr};
+use std::{
+    fmt::Display,
+    hash::{Hash, Hasher},
+    str::FromStr,
+};
 
-use super::{Move, ParseError};
+use super::{Move, PackedMove, ParseError};
+
+/// The number of moves that a [`MoveLine`] can store without allocating.
+pub const INLINE_MOVE_LINE_CAPACITY: usize = 15;
 
 /// A line of moves.
-#[derive(Debug, PartialEq, Eq)]
-pub struct MoveLine(pub Vec<Move>);
+///
+/// Short lines are stored inline as [`PackedMove`]s,
+/// only lines longer than [`INLINE_MOVE_LINE_CAPACITY`] allocate on the heap.
+#[derive(Debug, Clone)]
+pub struct MoveLine(Moves);
+
+/// The storage of the moves of a line.
+#[derive(Debug, Clone)]
+enum Moves {
+    /// The moves are stored inline, only the first `len` moves are part of the line.
+    Inline {
+        len: u8,
+        moves: [PackedMove; INLINE_MOVE_LINE_CAPACITY],
+    },
+
+    /// The moves are stored on the heap.
+    Heap(Vec<PackedMove>),
+}
 
 impl MoveLine {
+    /// Create a new line without any moves.
+    pub const fn new() -> Self {
+        MoveLine(Moves::Inline {
+            len: 0,
+            moves: [PackedMove::NULL; INLINE_MOVE_LINE_CAPACITY],
+        })
+    }
+
     /// Determines if there are any moves in this line.
     pub fn is_empty(&self) -> bool {
-        self.0.is_empty()
+        self.len() == 0
     }
 
     /// The number of moves in this line.
     pub fn len(&self) -> usize {
-        self.0.len()
+        self.as_slice().len()
     }
 
     /// Add a move to the end of the line.
     pub fn push(&mut self, mv: Move) {
-        self.0.push(mv)
+        let packed = PackedMove::from(mv);
+
+        match &mut self.0 {
+            Moves::Inline { len, moves } if (*len as usize) < INLINE_MOVE_LINE_CAPACITY => {
+                moves[*len as usize] = packed;
+                *len += 1;
+            }
+            Moves::Inline { moves, .. } => {
+                let mut heap = Vec::with_capacity(INLINE_MOVE_LINE_CAPACITY * 2);
+                heap.extend_from_slice(moves);
+                heap.push(packed);
+                self.0 = Moves::Heap(heap);
+            }
+            Moves::Heap(moves) => moves.push(packed),
+        }
+    }
+
+    /// Remove all moves from the line.
+    ///
+    /// Heap storage is kept to be reused.
+    pub fn clear(&mut self) {
+        match &mut self.0 {
+            Moves::Inline { len, .. } => *len = 0,
+            Moves::Heap(moves) => moves.clear(),
+        }
+    }
+
+    /// Get the move at the given position of the line.
+    pub fn get(&self, index: usize) -> Option<Move> {
+        self.as_slice().get(index).map(|&packed| packed.into())
+    }
+
+    /// Get the first move of the line.
+    pub fn first(&self) -> Option<Move> {
+        self.get(0)
+    }
+
+    /// Iterate over the moves of the line.
+    pub fn iter(&self) -> impl Iterator<Item = Move> + '_ {
+        self.as_slice().iter().map(|&packed| packed.into())
+    }
+
+    /// The packed moves of the line.
+    pub fn as_slice(&self) -> &[PackedMove] {
+        match &self.0 {
+            Moves::Inline { len, moves } => &moves[..*len as usize],
+            Moves::Heap(moves) => moves,
+        }
+    }
+}
+
+impl PartialEq for MoveLine {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl Eq for MoveLine {}
+
+impl Hash for MoveLine {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state);
+    }
+}
+
+impl Default for MoveLine {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
 impl FromIterator<Move> for MoveLine {
     fn from_iter<T: IntoIterator<Item = Move>>(iter: T) -> Self {
-        MoveLine(iter.into_iter().collect())
+        let mut line = MoveLine::new();
+
+        for mv in iter {
+            line.push(mv);
+        }
+
+        line
     }
 }
 
 impl From<Vec<Move>> for MoveLine {
     fn from(moves: Vec<Move>) -> Self {
-        MoveLine(moves)
+        moves.into_iter().collect()
     }
 }
 
 impl From<MoveLine> for Vec<Move> {
     fn from(moves: MoveLine) -> Self {
-        moves.0
+        moves.iter().collect()
     }
 }
 
 impl Display for MoveLine {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let move_strings: Vec<_> = self.0.iter().map(|mv| mv.to_string()).collect();
-        let output = move_strings.join(" ");
+        for (index, mv) in self.iter().enumerate() {
+            if index > 0 {
+                write!(f, " ")?;
+            }
+
+            write!(f, "{mv}")?;
+        }
 
-        write!(f, "{output}")
+        Ok(())
     }
 }
 
@@ -64,7 +173,7 @@ impl FromStr for MoveLine {
 mod tests {
     use super::super::Move;
     use super::*;
-    use crate::chess::{File, Rank, Square};
+    use crate::chess::{DropPiece, File, Rank, Square};
     use rstest::rstest;
 
     #[rstest]
@@ -78,8 +187,56 @@ mod tests {
             Square::new(File::G, Rank::One),
         ),
     ].into(), "e2e4 e1g1")]
+    #[case(vec![
+        Move::new(
+            Square::new(File::E, Rank::Two),
+            Square::new(File::E, Rank::Four),
+        ),
+        Move::new_drop(DropPiece::Knight, Square::new(File::F, Rank::Six)),
+    ].into(), "e2e4 N@f6")]
     fn format_line(#[case] input: MoveLine, #[case] expected: String) {
         let actual = format!("{input}");
         assert_eq!(actual, expected);
     }
+
+    #[rstest]
+    #[case("e2e4 e7e5", 2)]
+    #[case("e2e4 d7d5 e4d5 Q@e4", 4)]
+    #[case("", 0)]
+    fn parse_line_ok(#[case] input: &str, #[case] expected_len: usize) {
+        let actual = input.parse::<MoveLine>().expect("This is a valid line");
+        assert_eq!(actual.len(), expected_len);
+        assert_eq!(actual.to_string(), input);
+    }
+
+    #[test]
+    fn push_beyond_inline_capacity() {
+        let cycle: Vec<Move> = "g1f3 g8f6 f3g1 f6g8".parse::<MoveLine>().unwrap().into();
+        let moves: Vec<Move> = cycle
+            .into_iter()
+            .cycle()
+            .take(INLINE_MOVE_LINE_CAPACITY + 5)
+            .collect();
+
+        let line = MoveLine::from(moves.clone());
+
+        assert_eq!(line.len(), INLINE_MOVE_LINE_CAPACITY + 5);
+        assert_eq!(Vec::from(line), moves);
+    }
+
+    #[rstest]
+    #[case("e2e4 e7e5")]
+    #[case("e2e4 e7e5 g1f3 b8c6 f1b5 a7a6 b5a4 g8f6 e1g1 f8e7 f1e1 b7b5")]
+    fn clear_line(#[case] input: &str) {
+        let mut line = input.parse::<MoveLine>().unwrap();
+        line.clear();
+
+        assert!(line.is_empty());
+        assert_eq!(line, MoveLine::new());
+    }
+
+    #[test]
+    fn move_line_is_small() {
+        assert!(std::mem::size_of::<MoveLine>() <= 32);
+    }
 }
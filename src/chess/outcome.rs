@@ -0,0 +1,49 @@
+use std::fmt::Display;
+
+use super::Color;
+
+/// The result of a finished game, formatted like in PGN, e.g. `1-0`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Outcome {
+    /// The given player has won the game.
+    Win(Color),
+
+    /// The game is drawn.
+    Draw,
+}
+
+impl Outcome {
+    /// The player that has won the game, `None` for a draw.
+    pub const fn winner(&self) -> Option<Color> {
+        match self {
+            Outcome::Win(color) => Some(*color),
+            Outcome::Draw => None,
+        }
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let outcome = match self {
+            Outcome::Win(Color::White) => "1-0",
+            Outcome::Win(Color::Black) => "0-1",
+            Outcome::Draw => "1/2-1/2",
+        };
+
+        write!(f, "{outcome}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(Outcome::Win(Color::White), "1-0")]
+    #[case(Outcome::Win(Color::Black), "0-1")]
+    #[case(Outcome::Draw, "1/2-1/2")]
+    fn format_outcome(#[case] input: Outcome, #[case] expected: String) {
+        assert_eq!(input.to_string(), expected);
+    }
+}
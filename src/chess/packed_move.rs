@@ -0,0 +1,164 @@
+use std::fmt::Display;
+
+use super::{DropPiece, Move, PromotionPiece, Square};
+
+/// A [`Move`] packed into 16 bits.
+///
+/// The bits are laid out as follows:
+///
+/// - bits 0-5: the index of the target square.
+/// - bits 6-11: the index of the source square, `0` for drops.
+/// - bits 12-15: the kind of the move:
+///   `0` for a normal move, `1`-`4` for a promotion to a knight, bishop, rook or queen,
+///   `5`-`9` for a drop of a pawn, knight, bishop, rook or queen
+///   and `10` for a promotion to a king in antichess.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PackedMove(u16);
+
+const SQUARE_MASK: u16 = 0b11_1111;
+const SOURCE_SHIFT: u16 = 6;
+const KIND_SHIFT: u16 = 12;
+
+const KIND_NORMAL: u16 = 0;
+const KIND_PROMOTION: u16 = 1;
+const KIND_DROP: u16 = 5;
+const KIND_KING_PROMOTION: u16 = 10;
+const KIND_END: u16 = 11;
+
+impl PackedMove {
+    /// The packed move `a1a1`, which is not a legal move.
+    ///
+    /// Can be used as a placeholder.
+    pub const NULL: PackedMove = PackedMove(0);
+
+    /// Get the packed move from its raw bits.
+    ///
+    /// Returns `None` if the bits don't encode a valid move.
+    pub const fn from_bits(bits: u16) -> Option<Self> {
+        let kind = bits >> KIND_SHIFT;
+        let source = (bits >> SOURCE_SHIFT) & SQUARE_MASK;
+
+        let is_drop = kind >= KIND_DROP && kind < KIND_KING_PROMOTION;
+
+        if kind >= KIND_END || (is_drop && source != 0) {
+            return None;
+        }
+
+        Some(PackedMove(bits))
+    }
+
+    /// The raw bits of the packed move.
+    pub const fn bits(&self) -> u16 {
+        self.0
+    }
+}
+
+impl From<Move> for PackedMove {
+    fn from(mv: Move) -> Self {
+        let target = mv.target().index() as u16;
+        let source = mv.source().map_or(0, |source| source.index() as u16);
+
+        let kind = if let Some(piece) = mv.drop_piece() {
+            KIND_DROP
+                + match piece {
+                    DropPiece::Pawn => 0,
+                    DropPiece::Knight => 1,
+                    DropPiece::Bishop => 2,
+                    DropPiece::Rook => 3,
+                    DropPiece::Queen => 4,
+                }
+        } else if let Some(promotion) = mv.promotion() {
+            match promotion {
+                PromotionPiece::Knight => KIND_PROMOTION,
+                PromotionPiece::Bishop => KIND_PROMOTION + 1,
+                PromotionPiece::Rook => KIND_PROMOTION + 2,
+                PromotionPiece::Queen => KIND_PROMOTION + 3,
+                PromotionPiece::King => KIND_KING_PROMOTION,
+            }
+        } else {
+            KIND_NORMAL
+        };
+
+        PackedMove(kind << KIND_SHIFT | source << SOURCE_SHIFT | target)
+    }
+}
+
+impl From<PackedMove> for Move {
+    fn from(packed: PackedMove) -> Self {
+        let square = |index: u16| {
+            Square::from_index(index as u8).expect("A masked square index is always in range")
+        };
+
+        let target = square(packed.0 & SQUARE_MASK);
+        let source = square((packed.0 >> SOURCE_SHIFT) & SQUARE_MASK);
+
+        match packed.0 >> KIND_SHIFT {
+            KIND_NORMAL => Move::new(source, target),
+            1 => Move::new_with_promotion(source, target, PromotionPiece::Knight),
+            2 => Move::new_with_promotion(source, target, PromotionPiece::Bishop),
+            3 => Move::new_with_promotion(source, target, PromotionPiece::Rook),
+            4 => Move::new_with_promotion(source, target, PromotionPiece::Queen),
+            5 => Move::new_drop(DropPiece::Pawn, target),
+            6 => Move::new_drop(DropPiece::Knight, target),
+            7 => Move::new_drop(DropPiece::Bishop, target),
+            8 => Move::new_drop(DropPiece::Rook, target),
+            9 => Move::new_drop(DropPiece::Queen, target),
+            KIND_KING_PROMOTION => Move::new_with_promotion(source, target, PromotionPiece::King),
+            _ => unreachable!("A packed move always has a valid kind"),
+        }
+    }
+}
+
+impl From<PackedMove> for u16 {
+    fn from(packed: PackedMove) -> Self {
+        packed.0
+    }
+}
+
+impl Display for PackedMove {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", Move::from(*self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("a1a2")]
+    #[case("e2e4")]
+    #[case("h8a1")]
+    #[case("e7e8n")]
+    #[case("b2a1b")]
+    #[case("h7h8r")]
+    #[case("e7e8q")]
+    #[case("d2d1k")]
+    #[case("P@a1")]
+    #[case("N@f7")]
+    #[case("B@c4")]
+    #[case("R@h8")]
+    #[case("Q@d1")]
+    fn pack_move_roundtrip(#[case] input: &str) {
+        let mv = input.parse::<Move>().expect("This is a valid move");
+        let packed = PackedMove::from(mv);
+
+        assert_eq!(Move::from(packed), mv);
+        assert_eq!(PackedMove::from_bits(packed.bits()), Some(packed));
+        assert_eq!(packed.to_string(), input);
+    }
+
+    #[rstest]
+    #[case(0b1011_0000_0000_0000)]
+    #[case(0b1111_0000_0000_0000)]
+    #[case(0b0101_0000_0100_0000)]
+    fn unpack_invalid_bits(#[case] bits: u16) {
+        assert_eq!(PackedMove::from_bits(bits), None);
+    }
+
+    #[test]
+    fn packed_move_is_two_bytes() {
+        assert_eq!(std::mem::size_of::<PackedMove>(), 2);
+    }
+}
@@ -0,0 +1,171 @@
+use std::{fmt::Display, str::FromStr};
+
+use super::{Color, DropPiece, ParseError, PromotionPiece};
+
+/// The kind of a chess piece, regardless of its color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PieceKind {
+    /// A pawn, denoted 'p'.
+    Pawn,
+
+    /// A knight, denoted 'n'.
+    Knight,
+
+    /// A bishop, denoted 'b'.
+    Bishop,
+
+    /// A rook, denoted 'r'.
+    Rook,
+
+    /// A queen, denoted 'q'.
+    Queen,
+
+    /// A king, denoted 'k'.
+    King,
+}
+
+impl PieceKind {
+    /// The piece as it can be dropped onto the board, `None` for the king.
+    pub const fn to_drop_piece(&self) -> Option<DropPiece> {
+        let piece = match self {
+            PieceKind::Pawn => DropPiece::Pawn,
+            PieceKind::Knight => DropPiece::Knight,
+            PieceKind::Bishop => DropPiece::Bishop,
+            PieceKind::Rook => DropPiece::Rook,
+            PieceKind::Queen => DropPiece::Queen,
+            PieceKind::King => return None,
+        };
+
+        Some(piece)
+    }
+}
+
+impl From<DropPiece> for PieceKind {
+    fn from(piece: DropPiece) -> Self {
+        match piece {
+            DropPiece::Pawn => PieceKind::Pawn,
+            DropPiece::Knight => PieceKind::Knight,
+            DropPiece::Bishop => PieceKind::Bishop,
+            DropPiece::Rook => PieceKind::Rook,
+            DropPiece::Queen => PieceKind::Queen,
+        }
+    }
+}
+
+impl From<PromotionPiece> for PieceKind {
+    fn from(piece: PromotionPiece) -> Self {
+        match piece {
+            PromotionPiece::Knight => PieceKind::Knight,
+            PromotionPiece::Bishop => PieceKind::Bishop,
+            PromotionPiece::Rook => PieceKind::Rook,
+            PromotionPiece::Queen => PieceKind::Queen,
+            PromotionPiece::King => PieceKind::King,
+        }
+    }
+}
+
+/// A chess piece of one of the players.
+///
+/// Like in FEN, the pieces of White are denoted by uppercase letters, e.g. 'N',
+/// and the pieces of Black by lowercase letters, e.g. 'n'.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Piece {
+    /// The player that the piece belongs to.
+    pub color: Color,
+
+    /// The kind of the piece.
+    pub kind: PieceKind,
+}
+
+impl Piece {
+    /// Create a new piece of the given player.
+    pub const fn new(color: Color, kind: PieceKind) -> Self {
+        Self { color, kind }
+    }
+}
+
+impl FromStr for Piece {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let kind = match s.to_ascii_lowercase().as_str() {
+            "p" => PieceKind::Pawn,
+            "n" => PieceKind::Knight,
+            "b" => PieceKind::Bishop,
+            "r" => PieceKind::Rook,
+            "q" => PieceKind::Queen,
+            "k" => PieceKind::King,
+            _ => return Err(ParseError),
+        };
+
+        let color = if s.chars().all(|c| c.is_ascii_uppercase()) {
+            Color::White
+        } else {
+            Color::Black
+        };
+
+        Ok(Piece::new(color, kind))
+    }
+}
+
+impl Display for Piece {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let piece = match self.kind {
+            PieceKind::Pawn => 'p',
+            PieceKind::Knight => 'n',
+            PieceKind::Bishop => 'b',
+            PieceKind::Rook => 'r',
+            PieceKind::Queen => 'q',
+            PieceKind::King => 'k',
+        };
+
+        match self.color {
+            Color::White => write!(f, "{}", piece.to_ascii_uppercase()),
+            Color::Black => write!(f, "{piece}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("P", Piece::new(Color::White, PieceKind::Pawn))]
+    #[case("N", Piece::new(Color::White, PieceKind::Knight))]
+    #[case("K", Piece::new(Color::White, PieceKind::King))]
+    #[case("b", Piece::new(Color::Black, PieceKind::Bishop))]
+    #[case("r", Piece::new(Color::Black, PieceKind::Rook))]
+    #[case("q", Piece::new(Color::Black, PieceKind::Queen))]
+    fn parse_and_format_piece(#[case] input: &str, #[case] expected: Piece) {
+        assert_eq!(input.parse(), Ok(expected));
+        assert_eq!(expected.to_string(), input);
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("x")]
+    #[case("1")]
+    #[case("pp")]
+    #[case("~")]
+    fn parse_piece_err(#[case] input: &str) {
+        let actual = input.parse::<Piece>();
+        assert!(actual.is_err());
+    }
+
+    #[test]
+    fn convert_drop_pieces() {
+        for piece in [
+            DropPiece::Pawn,
+            DropPiece::Knight,
+            DropPiece::Bishop,
+            DropPiece::Rook,
+            DropPiece::Queen,
+        ] {
+            assert_eq!(PieceKind::from(piece).to_drop_piece(), Some(piece));
+        }
+
+        assert_eq!(PieceKind::King.to_drop_piece(), None);
+    }
+}
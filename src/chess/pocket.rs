@@ -0,0 +1,118 @@
+use super::PieceKind;
+
+/// The captured pieces that a player can drop onto the board in crazyhouse.
+///
+/// Kings are never captured, so they can't be in the pocket.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Pocket {
+    /// The number of pawns, knights, bishops, rooks and queens.
+    counts: [u8; 5],
+}
+
+impl Pocket {
+    /// The pieces in the order they are written in FEN, from the most to the least valuable.
+    pub const PIECES: [PieceKind; 5] = [
+        PieceKind::Queen,
+        PieceKind::Rook,
+        PieceKind::Bishop,
+        PieceKind::Knight,
+        PieceKind::Pawn,
+    ];
+
+    /// Create a new empty pocket.
+    pub const fn new() -> Self {
+        Self { counts: [0; 5] }
+    }
+
+    /// The number of pieces of the given kind in the pocket.
+    pub const fn count(&self, piece: PieceKind) -> u8 {
+        match index(piece) {
+            Some(index) => self.counts[index],
+            None => 0,
+        }
+    }
+
+    /// Determines if the pocket contains no pieces.
+    pub fn is_empty(&self) -> bool {
+        self.counts.iter().all(|&count| count == 0)
+    }
+
+    /// Put a piece into the pocket.
+    ///
+    /// Returns `false` if the piece is a king, which can't be put into the pocket.
+    pub fn add(&mut self, piece: PieceKind) -> bool {
+        let Some(index) = index(piece) else {
+            return false;
+        };
+
+        let count = &mut self.counts[index];
+        *count = count.saturating_add(1);
+        true
+    }
+
+    /// Take a piece out of the pocket.
+    ///
+    /// Returns `false` if there is no such piece in the pocket.
+    pub fn remove(&mut self, piece: PieceKind) -> bool {
+        let Some(count) = index(piece).map(|index| &mut self.counts[index]) else {
+            return false;
+        };
+
+        if *count == 0 {
+            return false;
+        }
+
+        *count -= 1;
+        true
+    }
+
+    /// The pieces in the pocket, from the most to the least valuable.
+    pub fn pieces(&self) -> impl Iterator<Item = PieceKind> + '_ {
+        Self::PIECES
+            .into_iter()
+            .flat_map(|piece| (0..self.count(piece)).map(move |_| piece))
+    }
+}
+
+/// The index of the piece in the counts of the pocket, `None` for the king.
+const fn index(piece: PieceKind) -> Option<usize> {
+    let index = match piece {
+        PieceKind::Pawn => 0,
+        PieceKind::Knight => 1,
+        PieceKind::Bishop => 2,
+        PieceKind::Rook => 3,
+        PieceKind::Queen => 4,
+        PieceKind::King => return None,
+    };
+
+    Some(index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_and_remove_pieces() {
+        let mut pocket = Pocket::new();
+
+        assert!(pocket.add(PieceKind::Pawn));
+        assert!(pocket.add(PieceKind::Queen));
+        assert!(pocket.add(PieceKind::Pawn));
+        assert!(!pocket.add(PieceKind::King));
+
+        assert_eq!(pocket.count(PieceKind::Pawn), 2);
+        assert_eq!(pocket.count(PieceKind::King), 0);
+        assert_eq!(
+            pocket.pieces().collect::<Vec<_>>(),
+            [PieceKind::Queen, PieceKind::Pawn, PieceKind::Pawn]
+        );
+
+        assert!(pocket.remove(PieceKind::Queen));
+        assert!(!pocket.remove(PieceKind::Queen));
+        assert!(!pocket.remove(PieceKind::King));
+        assert!(pocket.remove(PieceKind::Pawn));
+        assert!(pocket.remove(PieceKind::Pawn));
+        assert!(pocket.is_empty());
+    }
+}
@@ -0,0 +1,1470 @@
+use std::fmt::Display;
+
+use super::{
+    Color, DropPiece, File, Move, Outcome, ParseError, Piece, PieceKind, Pocket, PromotionPiece,
+    Rank, Square, Variant,
+};
+
+/// The offsets of the squares that a knight can move to, as `(file, rank)`.
+const KNIGHT_OFFSETS: [(i8, i8); 8] = [
+    (1, 2),
+    (2, 1),
+    (2, -1),
+    (1, -2),
+    (-1, -2),
+    (-2, -1),
+    (-2, 1),
+    (-1, 2),
+];
+
+/// The directions that bishops move in, as `(file, rank)`.
+const BISHOP_DIRECTIONS: [(i8, i8); 4] = [(1, 1), (1, -1), (-1, -1), (-1, 1)];
+
+/// The directions that rooks move in, as `(file, rank)`.
+const ROOK_DIRECTIONS: [(i8, i8); 4] = [(0, 1), (1, 0), (0, -1), (-1, 0)];
+
+/// The offsets of the squares that a king can move to, as `(file, rank)`.
+const KING_OFFSETS: [(i8, i8); 8] = [
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+];
+
+/// The pieces that a pawn can promote to in all variants, from the most valuable.
+const PROMOTIONS: [PromotionPiece; 4] = [
+    PromotionPiece::Queen,
+    PromotionPiece::Rook,
+    PromotionPiece::Bishop,
+    PromotionPiece::Knight,
+];
+
+/// The castling rights, in the order they are written in FEN.
+const CASTLING_RIGHTS: [(char, Color, CastlingSide); 4] = [
+    ('K', Color::White, CastlingSide::King),
+    ('Q', Color::White, CastlingSide::Queen),
+    ('k', Color::Black, CastlingSide::King),
+    ('q', Color::Black, CastlingSide::Queen),
+];
+
+/// The side of the board that the king castles to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CastlingSide {
+    /// Castling short, with the rook on the h-file.
+    King,
+
+    /// Castling long, with the rook on the a-file.
+    Queen,
+}
+
+impl CastlingSide {
+    /// The files of the rook before castling, of the king after castling and of the rook after
+    /// castling.
+    const fn files(&self) -> (File, File, File) {
+        match self {
+            CastlingSide::King => (File::H, File::G, File::F),
+            CastlingSide::Queen => (File::A, File::C, File::D),
+        }
+    }
+}
+
+/// The move is not legal in the position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IllegalMove {
+    /// The move that is not legal.
+    pub mv: Move,
+}
+
+/// A chess position with the rules of a [`Variant`].
+///
+/// The position is read from and written as FEN, including the pockets of crazyhouse,
+/// e.g. `RNBQKBNR[Qp]` or `RNBQKBNR/Qp`, and the remaining checks of three-check, e.g. `3+3`.
+/// Promoted pieces in crazyhouse are marked with `~`, e.g. `Q~`,
+/// as they are put back into the pocket as pawns when captured.
+///
+/// It knows the legal moves and the end of the game in every variant,
+/// so that the engine framework and GUIs can play and adjudicate the games:
+///
+/// - Crazyhouse: captured pieces can be dropped, pawns not on the first or last rank.
+/// - Three-check: giving check for the third time wins.
+/// - King of the hill: moving the king to the center wins.
+/// - Atomic: captures explode all pieces except pawns around the target square,
+///   kings can't capture and exploding the king of the opponent wins.
+/// - Antichess: captures are compulsory, there is no check and losing all pieces or
+///   having no legal moves wins.
+/// - Horde: White has no king and its pawns may also advance two squares from the first rank,
+///   capturing all white pieces wins.
+///
+/// Draws by repetition, the fifty-move rule or insufficient material depend on the game
+/// and are left to the GUI.
+///
+/// ```
+/// use universal_chess_interface::chess::{Color, Outcome, Position, Variant};
+///
+/// let mut position = Position::new(Variant::KingOfTheHill);
+///
+/// for mv in ["e2e4", "a7a6", "e1e2", "a6a5", "e2e3", "a5a4", "e3d4"] {
+///     position.play(mv.parse().unwrap()).unwrap();
+/// }
+///
+/// assert_eq!(position.outcome(), Some(Outcome::Win(Color::White)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Position {
+    /// The rules that the game is played with.
+    variant: Variant,
+
+    /// The pieces on the board, indexed by [`Square::index`].
+    board: [Option<Piece>; 64],
+
+    /// The squares of the pieces that have been promoted, for crazyhouse.
+    promoted: u64,
+
+    /// The player to make the next move.
+    side_to_move: Color,
+
+    /// The remaining castling rights, as indices into [`CASTLING_RIGHTS`].
+    castling: [bool; 4],
+
+    /// The square that a pawn skipped with the last move, which can be captured en passant.
+    en_passant: Option<Square>,
+
+    /// The number of half-moves since the last capture or pawn move.
+    halfmove_clock: u32,
+
+    /// The number of the full move, starting at 1 and incremented after Black's move.
+    fullmove_number: u32,
+
+    /// The pieces that White and Black can drop onto the board, for crazyhouse.
+    pockets: [Pocket; 2],
+
+    /// The number of checks that White and Black still have to give, for three-check.
+    remaining_checks: [u8; 2],
+}
+
+impl Position {
+    /// The start position of the variant.
+    pub fn new(variant: Variant) -> Self {
+        Self::from_fen(variant, variant.start_fen()).expect("The start position is valid")
+    }
+
+    /// Read the position from FEN, with the extensions of the variant.
+    ///
+    /// The half-move clock and the move number can be omitted, as well as the pockets
+    /// and the remaining checks.
+    /// Castling rights without king or rook on their start squares are ignored.
+    pub fn from_fen(variant: Variant, fen: &str) -> Result<Self, ParseError> {
+        let mut fields = fen.split_ascii_whitespace().peekable();
+
+        let mut position = Position {
+            variant,
+            board: [None; 64],
+            promoted: 0,
+            side_to_move: Color::White,
+            castling: [false; 4],
+            en_passant: None,
+            halfmove_clock: 0,
+            fullmove_number: 1,
+            pockets: [Pocket::new(); 2],
+            remaining_checks: [3, 3],
+        };
+
+        let (placement, pocket) = split_pocket(variant, fields.next().ok_or(ParseError)?)?;
+        position.parse_placement(placement)?;
+
+        for piece in pocket.unwrap_or_default().chars() {
+            let piece = parse_piece(piece)?;
+
+            if !position.pockets[color_index(piece.color)].add(piece.kind) {
+                return Err(ParseError);
+            }
+        }
+
+        position.side_to_move = fields.next().ok_or(ParseError)?.parse()?;
+        position.castling = parse_castling(fields.next().ok_or(ParseError)?)?;
+        position.en_passant = match fields.next().ok_or(ParseError)? {
+            "-" => None,
+            square => Some(square.parse()?),
+        };
+
+        if variant.has_check_counters() {
+            if let Some(checks) = fields.next_if(|field| field.contains('+')) {
+                position.remaining_checks = parse_checks(checks)?;
+            }
+        }
+
+        if let Some(clock) = fields.next() {
+            position.halfmove_clock = clock.parse().map_err(|_| ParseError)?;
+        }
+
+        if let Some(number) = fields.next() {
+            position.fullmove_number = number.parse().map_err(|_| ParseError)?;
+        }
+
+        if fields.next().is_some() {
+            return Err(ParseError);
+        }
+
+        position.validate()?;
+        Ok(position)
+    }
+
+    /// The rules that the game is played with.
+    pub fn variant(&self) -> Variant {
+        self.variant
+    }
+
+    /// The player to make the next move.
+    pub fn side_to_move(&self) -> Color {
+        self.side_to_move
+    }
+
+    /// The piece on the given square, if there is one.
+    pub fn piece_at(&self, square: Square) -> Option<Piece> {
+        self.board[usize::from(square.index())]
+    }
+
+    /// The square that a pawn skipped with the last move, which can be captured en passant.
+    pub fn en_passant(&self) -> Option<Square> {
+        self.en_passant
+    }
+
+    /// The number of half-moves since the last capture or pawn move.
+    pub fn halfmove_clock(&self) -> u32 {
+        self.halfmove_clock
+    }
+
+    /// The number of the full move, starting at 1 and incremented after Black's move.
+    pub fn fullmove_number(&self) -> u32 {
+        self.fullmove_number
+    }
+
+    /// The pieces that the player can drop onto the board in crazyhouse.
+    pub fn pocket(&self, color: Color) -> &Pocket {
+        &self.pockets[color_index(color)]
+    }
+
+    /// The number of checks that the player still has to give to win three-check.
+    pub fn remaining_checks(&self, color: Color) -> u8 {
+        self.remaining_checks[color_index(color)]
+    }
+
+    /// Determines if the king of the side to move is in check.
+    ///
+    /// There is no check in antichess, and in atomic, kings next to each other are not in check.
+    pub fn is_check(&self) -> bool {
+        self.is_in_check(self.side_to_move)
+    }
+
+    /// All legal moves of the side to move.
+    ///
+    /// There are no legal moves once the game has ended.
+    pub fn legal_moves(&self) -> Vec<Move> {
+        if self.variant_outcome().is_some() {
+            return Vec::new();
+        }
+
+        let mut moves: Vec<_> = self
+            .pseudo_legal_moves()
+            .into_iter()
+            .filter(|&mv| self.is_legal(mv))
+            .collect();
+
+        // Captures are compulsory
+        if self.variant == Variant::Antichess && moves.iter().any(|&mv| self.is_capture(mv)) {
+            moves.retain(|&mv| self.is_capture(mv));
+        }
+
+        moves
+    }
+
+    /// Play the move, if it's legal.
+    pub fn play(&mut self, mv: Move) -> Result<(), IllegalMove> {
+        if !self.legal_moves().contains(&mv) {
+            return Err(IllegalMove { mv });
+        }
+
+        self.make(mv);
+        Ok(())
+    }
+
+    /// The result of the game, if it has ended.
+    ///
+    /// Besides checkmate and stalemate, this covers the winning conditions of the variants.
+    pub fn outcome(&self) -> Option<Outcome> {
+        if let Some(outcome) = self.variant_outcome() {
+            return Some(outcome);
+        }
+
+        if !self.legal_moves().is_empty() {
+            return None;
+        }
+
+        let outcome = if self.variant == Variant::Antichess {
+            Outcome::Win(self.side_to_move)
+        } else if self.is_check() {
+            Outcome::Win(self.side_to_move.opposite())
+        } else {
+            Outcome::Draw
+        };
+
+        Some(outcome)
+    }
+
+    /// Count the leaf nodes of the tree of legal moves up to the given depth.
+    ///
+    /// This is used to test move generators.
+    pub fn perft(&self, depth: u32) -> u64 {
+        if depth == 0 {
+            return 1;
+        }
+
+        let moves = self.legal_moves();
+
+        if depth == 1 {
+            return moves.len() as u64;
+        }
+
+        moves
+            .into_iter()
+            .map(|mv| {
+                let mut child = self.clone();
+                child.make(mv);
+                child.perft(depth - 1)
+            })
+            .sum()
+    }
+
+    /// Mirror the position vertically and swap the colors of all pieces.
+    ///
+    /// The evaluation of the flipped position should be the same, which is useful to find
+    /// asymmetries in the evaluation of an engine.
+    /// In horde, the flipped position is not a valid game, as Black has the pawns.
+    pub fn flipped(&self) -> Self {
+        let mut board = [None; 64];
+
+        for (index, piece) in self.board.iter().enumerate() {
+            board[index ^ 56] = piece.map(|piece| Piece::new(piece.color.opposite(), piece.kind));
+        }
+
+        let [white_king, white_queen, black_king, black_queen] = self.castling;
+        let [white_pocket, black_pocket] = self.pockets;
+        let [white_checks, black_checks] = self.remaining_checks;
+
+        Position {
+            variant: self.variant,
+            board,
+            promoted: self.promoted.swap_bytes(),
+            side_to_move: self.side_to_move.opposite(),
+            castling: [black_king, black_queen, white_king, white_queen],
+            en_passant: self.en_passant.map(mirror),
+            halfmove_clock: self.halfmove_clock,
+            fullmove_number: self.fullmove_number,
+            pockets: [black_pocket, white_pocket],
+            remaining_checks: [black_checks, white_checks],
+        }
+    }
+
+    /// Read the pieces on the board, from the eighth to the first rank.
+    fn parse_placement(&mut self, placement: &str) -> Result<(), ParseError> {
+        let ranks: Vec<_> = placement.split('/').collect();
+
+        if ranks.len() != 8 {
+            return Err(ParseError);
+        }
+
+        for (rank, pieces) in (0..8).rev().zip(ranks) {
+            let mut file = 0;
+            let mut pieces = pieces.chars().peekable();
+
+            while let Some(piece) = pieces.next() {
+                if let Some(empty @ 1..=8) = piece.to_digit(10) {
+                    file += empty as u8;
+                } else {
+                    let square = Square::from_index(rank * 8 + file)
+                        .filter(|_| file < 8)
+                        .ok_or(ParseError)?;
+                    self.set(square, Some(parse_piece(piece)?));
+
+                    if pieces.next_if_eq(&'~').is_some() {
+                        if !self.variant.has_drops() {
+                            return Err(ParseError);
+                        }
+
+                        self.promoted |= 1 << square.index();
+                    }
+
+                    file += 1;
+                }
+
+                if file > 8 {
+                    return Err(ParseError);
+                }
+            }
+
+            if file != 8 {
+                return Err(ParseError);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check that the position can occur in a game of the variant.
+    fn validate(&mut self) -> Result<(), ParseError> {
+        let king_count = |color| {
+            self.board
+                .iter()
+                .filter(|&&piece| piece == Some(Piece::new(color, PieceKind::King)))
+                .count()
+        };
+        let kings = (king_count(Color::White), king_count(Color::Black));
+
+        let valid_kings = match self.variant {
+            Variant::Antichess => true,
+            Variant::Horde => kings == (0, 1),
+            // The king may have exploded
+            Variant::Atomic => matches!(kings, (0, 1) | (1, 0) | (1, 1)),
+            _ => kings == (1, 1),
+        };
+
+        if !valid_kings {
+            return Err(ParseError);
+        }
+
+        for (index, piece) in self.board.iter().enumerate() {
+            let is_back_rank = !(8..56).contains(&index);
+            let is_horde_pawn = self.variant == Variant::Horde
+                && index < 8
+                && *piece == Some(Piece::new(Color::White, PieceKind::Pawn));
+
+            if is_back_rank && !is_horde_pawn && piece.is_some_and(|p| p.kind == PieceKind::Pawn) {
+                return Err(ParseError);
+            }
+        }
+
+        // The opponent would be able to capture the king
+        if self.is_in_check(self.side_to_move.opposite()) {
+            return Err(ParseError);
+        }
+
+        let en_passant_rank = match self.side_to_move {
+            Color::White => Rank::Six,
+            Color::Black => Rank::Three,
+        };
+
+        if self
+            .en_passant
+            .is_some_and(|square| square.rank != en_passant_rank)
+        {
+            return Err(ParseError);
+        }
+
+        for (index, (_, color, side)) in CASTLING_RIGHTS.into_iter().enumerate() {
+            let rank = back_rank(color);
+            let (rook_file, _, _) = side.files();
+
+            self.castling[index] &= self.variant != Variant::Antichess
+                && self.piece_at(Square::new(File::E, rank))
+                    == Some(Piece::new(color, PieceKind::King))
+                && self.piece_at(Square::new(rook_file, rank))
+                    == Some(Piece::new(color, PieceKind::Rook));
+        }
+
+        Ok(())
+    }
+
+    /// Put the piece onto the square, or remove the piece on it.
+    fn set(&mut self, square: Square, piece: Option<Piece>) {
+        self.board[usize::from(square.index())] = piece;
+        self.promoted &= !(1 << square.index());
+    }
+
+    /// Determines if the piece on the square has been promoted.
+    fn is_promoted(&self, square: Square) -> bool {
+        self.promoted & (1 << square.index()) != 0
+    }
+
+    /// The square of the king of the player, if there is one.
+    fn king(&self, color: Color) -> Option<Square> {
+        let king = Some(Piece::new(color, PieceKind::King));
+
+        self.board
+            .iter()
+            .position(|&piece| piece == king)
+            .and_then(|index| Square::from_index(index as u8))
+    }
+
+    /// Determines if the player has any pieces left.
+    fn has_pieces(&self, color: Color) -> bool {
+        self.board
+            .iter()
+            .any(|piece| piece.is_some_and(|piece| piece.color == color))
+    }
+
+    /// Determines if the king of the player is in check.
+    fn is_in_check(&self, color: Color) -> bool {
+        if self.variant == Variant::Antichess {
+            return false;
+        }
+
+        self.king(color)
+            .is_some_and(|king| self.is_king_attacked(king, color))
+    }
+
+    /// Determines if a king of the player would be attacked on the square.
+    fn is_king_attacked(&self, square: Square, color: Color) -> bool {
+        let opponent = color.opposite();
+
+        if self.variant == Variant::Atomic {
+            // Capturing a king next to the own king would explode both,
+            // so kings can't capture at all
+            let is_next_to_king = self
+                .king(opponent)
+                .is_some_and(|king| is_adjacent(king, square));
+
+            return !is_next_to_king && self.is_attacked(square, opponent, false);
+        }
+
+        self.is_attacked(square, opponent, true)
+    }
+
+    /// Determines if any piece of the player attacks the square.
+    fn is_attacked(&self, square: Square, by: Color, include_king: bool) -> bool {
+        let is_piece = |offset: Option<Square>, kinds: &[PieceKind]| {
+            offset
+                .and_then(|square| self.piece_at(square))
+                .is_some_and(|piece| piece.color == by && kinds.contains(&piece.kind))
+        };
+
+        let pawn_direction = pawn_direction(by);
+        let attacked_by_pawn = [-1, 1]
+            .into_iter()
+            .any(|file| is_piece(offset(square, file, -pawn_direction), &[PieceKind::Pawn]));
+        let attacked_by_knight = KNIGHT_OFFSETS
+            .into_iter()
+            .any(|(file, rank)| is_piece(offset(square, file, rank), &[PieceKind::Knight]));
+        let attacked_by_king = include_king
+            && KING_OFFSETS
+                .into_iter()
+                .any(|(file, rank)| is_piece(offset(square, file, rank), &[PieceKind::King]));
+        let attacked_by_slider = |directions: [(i8, i8); 4], kinds: &[PieceKind]| {
+            directions
+                .into_iter()
+                .any(|direction| is_piece(self.ray_end(square, direction), kinds))
+        };
+
+        attacked_by_pawn
+            || attacked_by_knight
+            || attacked_by_king
+            || attacked_by_slider(BISHOP_DIRECTIONS, &[PieceKind::Bishop, PieceKind::Queen])
+            || attacked_by_slider(ROOK_DIRECTIONS, &[PieceKind::Rook, PieceKind::Queen])
+    }
+
+    /// The first occupied square in the direction, if there is one.
+    fn ray_end(&self, from: Square, (file, rank): (i8, i8)) -> Option<Square> {
+        let mut square = offset(from, file, rank)?;
+
+        while self.piece_at(square).is_none() {
+            square = offset(square, file, rank)?;
+        }
+
+        Some(square)
+    }
+
+    /// The outcome of the game by the special rules of the variant, if it has ended.
+    fn variant_outcome(&self) -> Option<Outcome> {
+        let colors = [self.side_to_move, self.side_to_move.opposite()];
+
+        match self.variant {
+            Variant::Standard | Variant::Crazyhouse => None,
+            Variant::ThreeCheck => colors
+                .into_iter()
+                .find(|&color| self.remaining_checks(color) == 0)
+                .map(Outcome::Win),
+            Variant::KingOfTheHill => colors
+                .into_iter()
+                .find(|&color| {
+                    self.king(color).is_some_and(|king| {
+                        matches!(king.file, File::D | File::E)
+                            && matches!(king.rank, Rank::Four | Rank::Five)
+                    })
+                })
+                .map(Outcome::Win),
+            Variant::Atomic => colors
+                .into_iter()
+                .find(|&color| self.king(color).is_none())
+                .map(|color| Outcome::Win(color.opposite())),
+            Variant::Antichess => colors
+                .into_iter()
+                .find(|&color| !self.has_pieces(color))
+                .map(Outcome::Win),
+            Variant::Horde => {
+                (!self.has_pieces(Color::White)).then_some(Outcome::Win(Color::Black))
+            }
+        }
+    }
+
+    /// All moves of the side to move, without checking if the own king is left in check.
+    fn pseudo_legal_moves(&self) -> Vec<Move> {
+        let mut moves = Vec::new();
+
+        for index in 0..64 {
+            let Some(from) = Square::from_index(index) else {
+                continue;
+            };
+
+            match self.piece_at(from) {
+                Some(piece) if piece.color == self.side_to_move => {
+                    self.piece_moves(from, piece.kind, &mut moves)
+                }
+                _ => {}
+            }
+        }
+
+        if self.variant.has_drops() {
+            self.drop_moves(&mut moves);
+        }
+
+        moves
+    }
+
+    /// The moves of the piece on the square.
+    fn piece_moves(&self, from: Square, kind: PieceKind, moves: &mut Vec<Move>) {
+        match kind {
+            PieceKind::Pawn => self.pawn_moves(from, moves),
+            PieceKind::Knight => self.step_moves(from, &KNIGHT_OFFSETS, true, moves),
+            PieceKind::Bishop => self.slide_moves(from, &BISHOP_DIRECTIONS, moves),
+            PieceKind::Rook => self.slide_moves(from, &ROOK_DIRECTIONS, moves),
+            PieceKind::Queen => {
+                self.slide_moves(from, &BISHOP_DIRECTIONS, moves);
+                self.slide_moves(from, &ROOK_DIRECTIONS, moves);
+            }
+            PieceKind::King => {
+                // In atomic, the king would explode itself
+                let can_capture = self.variant != Variant::Atomic;
+                self.step_moves(from, &KING_OFFSETS, can_capture, moves);
+                self.castling_moves(from, moves);
+            }
+        }
+    }
+
+    /// The moves of a pawn, including promotions and en passant.
+    fn pawn_moves(&self, from: Square, moves: &mut Vec<Move>) {
+        let color = self.side_to_move;
+        let direction = pawn_direction(color);
+        let is_empty = |square: Square| self.piece_at(square).is_none();
+
+        if let Some(to) = offset(from, 0, direction).filter(|&to| is_empty(to)) {
+            self.push_pawn_move(from, to, moves);
+
+            let can_advance_two = from.rank == pawn_rank(color)
+                || (self.variant == Variant::Horde && from.rank == back_rank(color));
+
+            if let Some(to) = offset(to, 0, direction).filter(|&to| can_advance_two && is_empty(to))
+            {
+                moves.push(Move::new(from, to));
+            }
+        }
+
+        for file in [-1, 1] {
+            let Some(to) = offset(from, file, direction) else {
+                continue;
+            };
+
+            let is_capture = self.piece_at(to).is_some_and(|piece| piece.color != color);
+
+            if is_capture || self.is_en_passant(Move::new(from, to)) {
+                self.push_pawn_move(from, to, moves);
+            }
+        }
+    }
+
+    /// Add the pawn move, or all promotions if the pawn reaches the last rank.
+    fn push_pawn_move(&self, from: Square, to: Square, moves: &mut Vec<Move>) {
+        if to.rank != back_rank(self.side_to_move.opposite()) {
+            moves.push(Move::new(from, to));
+            return;
+        }
+
+        for promotion in PROMOTIONS {
+            moves.push(Move::new_with_promotion(from, to, promotion));
+        }
+
+        // The king is a regular piece in antichess
+        if self.variant == Variant::Antichess {
+            moves.push(Move::new_with_promotion(from, to, PromotionPiece::King));
+        }
+    }
+
+    /// The moves of a knight or king, one step by each offset.
+    fn step_moves(
+        &self,
+        from: Square,
+        offsets: &[(i8, i8)],
+        can_capture: bool,
+        moves: &mut Vec<Move>,
+    ) {
+        for &(file, rank) in offsets {
+            let Some(to) = offset(from, file, rank) else {
+                continue;
+            };
+
+            match self.piece_at(to) {
+                None => moves.push(Move::new(from, to)),
+                Some(piece) if can_capture && piece.color != self.side_to_move => {
+                    moves.push(Move::new(from, to))
+                }
+                Some(_) => {}
+            }
+        }
+    }
+
+    /// The moves of a bishop, rook or queen, sliding in the directions until blocked.
+    fn slide_moves(&self, from: Square, directions: &[(i8, i8)], moves: &mut Vec<Move>) {
+        for &(file, rank) in directions {
+            let mut to = offset(from, file, rank);
+
+            while let Some(square) = to {
+                match self.piece_at(square) {
+                    None => moves.push(Move::new(from, square)),
+                    Some(piece) => {
+                        if piece.color != self.side_to_move {
+                            moves.push(Move::new(from, square));
+                        }
+
+                        break;
+                    }
+                }
+
+                to = offset(square, file, rank);
+            }
+        }
+    }
+
+    /// The castling moves of the king, written as king moves like `e1g1`.
+    fn castling_moves(&self, from: Square, moves: &mut Vec<Move>) {
+        let color = self.side_to_move;
+
+        if self.is_in_check(color) {
+            return;
+        }
+
+        for (index, (_, right_color, side)) in CASTLING_RIGHTS.into_iter().enumerate() {
+            if !self.castling[index] || right_color != color {
+                continue;
+            }
+
+            let (rook_file, king_file, _) = side.files();
+            let rank = from.rank;
+            let between = |from: File, to: File| {
+                let (min, max) = (from.index().min(to.index()), from.index().max(to.index()));
+                (min + 1..max).filter_map(|file| Square::from_index(rank.index() * 8 + file))
+            };
+
+            let is_path_empty =
+                between(from.file, rook_file).all(|square| self.piece_at(square).is_none());
+            // The king must not pass a square where it would be in check
+            let is_path_safe =
+                between(from.file, king_file).all(|square| !self.is_king_attacked(square, color));
+
+            if is_path_empty && is_path_safe {
+                moves.push(Move::new(from, Square::new(king_file, rank)));
+            }
+        }
+    }
+
+    /// The drops of the pieces in the pocket of the side to move onto empty squares.
+    fn drop_moves(&self, moves: &mut Vec<Move>) {
+        let pocket = self.pocket(self.side_to_move);
+
+        for piece in Pocket::PIECES {
+            let Some(drop_piece) = piece.to_drop_piece().filter(|_| pocket.count(piece) > 0) else {
+                continue;
+            };
+
+            for index in 0..64 {
+                let Some(to) = Square::from_index(index) else {
+                    continue;
+                };
+
+                let is_back_rank = matches!(to.rank, Rank::One | Rank::Eight);
+
+                if self.piece_at(to).is_none() && !(piece == PieceKind::Pawn && is_back_rank) {
+                    moves.push(Move::new_drop(drop_piece, to));
+                }
+            }
+        }
+    }
+
+    /// Determines if the move doesn't leave the own king in check,
+    /// or explode it in atomic.
+    fn is_legal(&self, mv: Move) -> bool {
+        let color = self.side_to_move;
+        let mut after = self.clone();
+        after.make(mv);
+
+        match self.variant {
+            Variant::Antichess => true,
+            // Exploding the king of the opponent wins, even if the own king is attacked
+            Variant::Atomic => {
+                after.king(color).is_some()
+                    && (after.king(color.opposite()).is_none() || !after.is_in_check(color))
+            }
+            _ => !after.is_in_check(color),
+        }
+    }
+
+    /// Determines if the move captures a piece.
+    fn is_capture(&self, mv: Move) -> bool {
+        mv.source().is_some() && self.piece_at(mv.target()).is_some() || self.is_en_passant(mv)
+    }
+
+    /// Determines if the move is a pawn capturing en passant.
+    fn is_en_passant(&self, mv: Move) -> bool {
+        let Some(from) = mv.source() else {
+            return false;
+        };
+
+        let color = self.side_to_move;
+        let captured = offset(mv.target(), 0, -pawn_direction(color));
+
+        Some(mv.target()) == self.en_passant
+            && from.file != mv.target().file
+            && self.piece_at(from) == Some(Piece::new(color, PieceKind::Pawn))
+            && captured.and_then(|square| self.piece_at(square))
+                == Some(Piece::new(color.opposite(), PieceKind::Pawn))
+    }
+
+    /// Play the move without checking if it's legal.
+    fn make(&mut self, mv: Move) {
+        let color = self.side_to_move;
+        let target = mv.target();
+        let is_en_passant = self.is_en_passant(mv);
+
+        self.en_passant = None;
+        self.halfmove_clock += 1;
+
+        match (mv.source(), mv.drop_piece()) {
+            (Some(source), _) => {
+                let Some(piece) = self.piece_at(source) else {
+                    return;
+                };
+                let was_promoted = self.is_promoted(source);
+                self.set(source, None);
+
+                let captured_square = if is_en_passant {
+                    offset(target, 0, -pawn_direction(color)).unwrap_or(target)
+                } else {
+                    target
+                };
+                let captured = self.piece_at(captured_square);
+                let captured_was_promoted = self.is_promoted(captured_square);
+                self.set(captured_square, None);
+
+                if piece.kind == PieceKind::King
+                    && self.variant != Variant::Antichess
+                    && source.file.index().abs_diff(target.file.index()) == 2
+                {
+                    self.move_castling_rook(source, target);
+                }
+
+                let kind = mv.promotion().map_or(piece.kind, PieceKind::from);
+                self.set(target, Some(Piece::new(color, kind)));
+
+                if self.variant.has_drops() && (was_promoted || mv.promotion().is_some()) {
+                    self.promoted |= 1 << target.index();
+                }
+
+                if piece.kind == PieceKind::Pawn || captured.is_some() {
+                    self.halfmove_clock = 0;
+                }
+
+                if piece.kind == PieceKind::Pawn
+                    && source.rank == pawn_rank(color)
+                    && source.rank.index().abs_diff(target.rank.index()) == 2
+                {
+                    self.en_passant = offset(source, 0, pawn_direction(color));
+                }
+
+                self.remove_castling_rights(source);
+                self.remove_castling_rights(target);
+
+                if let Some(captured) = captured {
+                    self.capture(target, captured, captured_was_promoted);
+                }
+            }
+            (None, Some(piece)) => {
+                self.pockets[color_index(color)].remove(piece.into());
+                self.set(target, Some(Piece::new(color, piece.into())));
+
+                if piece == DropPiece::Pawn {
+                    self.halfmove_clock = 0;
+                }
+            }
+            (None, None) => {}
+        }
+
+        if color == Color::Black {
+            self.fullmove_number += 1;
+        }
+
+        self.side_to_move = color.opposite();
+
+        if self.variant.has_check_counters() && self.is_in_check(color.opposite()) {
+            let checks = &mut self.remaining_checks[color_index(color)];
+            *checks = checks.saturating_sub(1);
+        }
+    }
+
+    /// Apply the effects of the capture by the piece on the target square.
+    fn capture(&mut self, target: Square, captured: Piece, was_promoted: bool) {
+        match self.variant {
+            Variant::Crazyhouse => {
+                let piece = if was_promoted {
+                    PieceKind::Pawn
+                } else {
+                    captured.kind
+                };
+
+                self.pockets[color_index(self.side_to_move)].add(piece);
+            }
+            Variant::Atomic => {
+                // The capturing piece explodes together with all pieces around except pawns
+                self.set(target, None);
+
+                for (file, rank) in KING_OFFSETS {
+                    let Some(square) = offset(target, file, rank) else {
+                        continue;
+                    };
+
+                    if self
+                        .piece_at(square)
+                        .is_some_and(|piece| piece.kind != PieceKind::Pawn)
+                    {
+                        self.set(square, None);
+                        self.remove_castling_rights(square);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Move the rook of the castling king from the corner next to the king.
+    fn move_castling_rook(&mut self, source: Square, target: Square) {
+        let side = if target.file.index() > source.file.index() {
+            CastlingSide::King
+        } else {
+            CastlingSide::Queen
+        };
+        let (rook_file, _, rook_target_file) = side.files();
+        let rook_square = Square::new(rook_file, source.rank);
+
+        let rook = self.piece_at(rook_square);
+        self.set(rook_square, None);
+        self.set(Square::new(rook_target_file, source.rank), rook);
+    }
+
+    /// Remove the castling rights that need a king or rook on the square.
+    fn remove_castling_rights(&mut self, square: Square) {
+        for (index, (_, color, side)) in CASTLING_RIGHTS.into_iter().enumerate() {
+            let (rook_file, _, _) = side.files();
+
+            if square.rank == back_rank(color)
+                && (square.file == File::E || square.file == rook_file)
+            {
+                self.castling[index] = false;
+            }
+        }
+    }
+}
+
+impl Display for Position {
+    /// Write the position as FEN, with the extensions of the variant.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut output = String::new();
+
+        for rank in (0..8).rev() {
+            let mut empty = 0;
+
+            for file in 0..8 {
+                let square = Square::from_index(rank * 8 + file).expect("The square is in range");
+
+                let Some(piece) = self.piece_at(square) else {
+                    empty += 1;
+                    continue;
+                };
+
+                if empty > 0 {
+                    output += &empty.to_string();
+                    empty = 0;
+                }
+
+                output += &piece.to_string();
+
+                if self.is_promoted(square) {
+                    output.push('~');
+                }
+            }
+
+            if empty > 0 {
+                output += &empty.to_string();
+            }
+
+            if rank > 0 {
+                output.push('/');
+            }
+        }
+
+        if self.variant.has_drops() {
+            output.push('[');
+
+            for color in [Color::White, Color::Black] {
+                for piece in self.pocket(color).pieces() {
+                    output += &Piece::new(color, piece).to_string();
+                }
+            }
+
+            output.push(']');
+        }
+
+        output += &format!(" {}", self.side_to_move);
+
+        let castling: String = CASTLING_RIGHTS
+            .iter()
+            .zip(self.castling)
+            .filter(|(_, has_right)| *has_right)
+            .map(|((right, _, _), _)| right)
+            .collect();
+
+        if castling.is_empty() {
+            output += " -";
+        } else {
+            output += &format!(" {castling}");
+        }
+
+        match self.en_passant {
+            Some(square) => output += &format!(" {square}"),
+            None => output += " -",
+        }
+
+        if self.variant.has_check_counters() {
+            output += &format!(
+                " {}+{}",
+                self.remaining_checks(Color::White),
+                self.remaining_checks(Color::Black)
+            );
+        }
+
+        output += &format!(" {} {}", self.halfmove_clock, self.fullmove_number);
+
+        write!(f, "{output}")
+    }
+}
+
+/// Split the pocket from the pieces on the board, for drop variants.
+///
+/// The pocket is written in brackets, e.g. `RNBQKBNR[Qp]`, or as ninth rank, e.g. `RNBQKBNR/Qp`.
+fn split_pocket(variant: Variant, placement: &str) -> Result<(&str, Option<&str>), ParseError> {
+    if let Some((board, pocket)) = placement.split_once('[') {
+        let pocket = pocket.strip_suffix(']').ok_or(ParseError)?;
+
+        return if variant.has_drops() {
+            Ok((board, Some(pocket)))
+        } else {
+            Err(ParseError)
+        };
+    }
+
+    match placement.rsplit_once('/') {
+        Some((board, pocket)) if variant.has_drops() && placement.matches('/').count() == 8 => {
+            Ok((board, Some(pocket)))
+        }
+        _ => Ok((placement, None)),
+    }
+}
+
+/// Parse a piece written as letter, e.g. `N` or `n`.
+fn parse_piece(piece: char) -> Result<Piece, ParseError> {
+    piece.encode_utf8(&mut [0; 4]).parse()
+}
+
+/// Parse the castling rights, e.g. `KQkq` or `-`.
+fn parse_castling(castling: &str) -> Result<[bool; 4], ParseError> {
+    let mut rights = [false; 4];
+
+    if castling == "-" {
+        return Ok(rights);
+    }
+
+    for right in castling.chars() {
+        let index = CASTLING_RIGHTS
+            .iter()
+            .position(|(name, _, _)| *name == right)
+            .ok_or(ParseError)?;
+
+        if rights[index] {
+            return Err(ParseError);
+        }
+
+        rights[index] = true;
+    }
+
+    Ok(rights)
+}
+
+/// Parse the remaining checks of White and Black, e.g. `3+3`.
+fn parse_checks(checks: &str) -> Result<[u8; 2], ParseError> {
+    let (white, black) = checks.split_once('+').ok_or(ParseError)?;
+    let parse = |checks: &str| match checks.parse() {
+        Ok(checks @ 0..=3) => Ok(checks),
+        _ => Err(ParseError),
+    };
+
+    Ok([parse(white)?, parse(black)?])
+}
+
+/// The index of the player in arrays with one entry for White and one for Black.
+const fn color_index(color: Color) -> usize {
+    match color {
+        Color::White => 0,
+        Color::Black => 1,
+    }
+}
+
+/// The direction that the pawns of the player move in, `1` for White and `-1` for Black.
+const fn pawn_direction(color: Color) -> i8 {
+    match color {
+        Color::White => 1,
+        Color::Black => -1,
+    }
+}
+
+/// The rank that the pieces of the player start on.
+const fn back_rank(color: Color) -> Rank {
+    match color {
+        Color::White => Rank::One,
+        Color::Black => Rank::Eight,
+    }
+}
+
+/// The rank that the pawns of the player start on.
+const fn pawn_rank(color: Color) -> Rank {
+    match color {
+        Color::White => Rank::Two,
+        Color::Black => Rank::Seven,
+    }
+}
+
+/// The square at the offset from the given square, if it's on the board.
+fn offset(square: Square, file: i8, rank: i8) -> Option<Square> {
+    let file = File::from_index(square.file.index().checked_add_signed(file)?)?;
+    let rank = Rank::from_index(square.rank.index().checked_add_signed(rank)?)?;
+
+    Some(Square::new(file, rank))
+}
+
+/// The square on the same file, mirrored to the other side of the board.
+fn mirror(square: Square) -> Square {
+    Square::from_index(square.index() ^ 56).expect("A mirrored square is on the board")
+}
+
+/// Determines if the squares are next to each other, including diagonally.
+fn is_adjacent(a: Square, b: Square) -> bool {
+    a != b
+        && a.file.index().abs_diff(b.file.index()) <= 1
+        && a.rank.index().abs_diff(b.rank.index()) <= 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn play(position: &mut Position, moves: &[&str]) {
+        for mv in moves {
+            position.play(mv.parse().unwrap()).unwrap();
+        }
+    }
+
+    #[rstest]
+    #[case(Variant::Standard)]
+    #[case(Variant::Crazyhouse)]
+    #[case(Variant::ThreeCheck)]
+    #[case(Variant::KingOfTheHill)]
+    #[case(Variant::Atomic)]
+    #[case(Variant::Antichess)]
+    #[case(Variant::Horde)]
+    fn format_start_position(#[case] variant: Variant) {
+        assert_eq!(Position::new(variant).to_string(), variant.start_fen());
+    }
+
+    #[rstest]
+    #[case(
+        Variant::Crazyhouse,
+        "r1bqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR/Qnp w KQkq - 0 1",
+        "r1bqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR[Qnp] w KQkq - 0 1"
+    )]
+    #[case(
+        Variant::Crazyhouse,
+        "4k3/8/8/8/8/8/8/4K2Q~[pr] b - - 5 30",
+        "4k3/8/8/8/8/8/8/4K2Q~[rp] b - - 5 30"
+    )]
+    #[case(
+        Variant::ThreeCheck,
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 1+3",
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 1+3 0 1"
+    )]
+    #[case(
+        Variant::ThreeCheck,
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 3+3 0 1"
+    )]
+    #[case(
+        Variant::Standard,
+        "r3k2r/8/8/8/8/8/8/R3K3 w KQkq - 0 1",
+        "r3k2r/8/8/8/8/8/8/R3K3 w Qkq - 0 1"
+    )]
+    #[case(
+        Variant::Standard,
+        "rnbqkbnr/pppp1ppp/8/8/4pP2/8/PPPPP1PP/RNBQKBNR b KQkq f3 0 3",
+        "rnbqkbnr/pppp1ppp/8/8/4pP2/8/PPPPP1PP/RNBQKBNR b KQkq f3 0 3"
+    )]
+    fn parse_and_format_fen(#[case] variant: Variant, #[case] fen: &str, #[case] expected: &str) {
+        let position = Position::from_fen(variant, fen).unwrap();
+        assert_eq!(position.to_string(), expected);
+    }
+
+    #[rstest]
+    #[case(Variant::Standard, "")]
+    #[case(Variant::Standard, "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP w KQkq - 0 1")]
+    #[case(
+        Variant::Standard,
+        "rnbqkbnr/pppppppp/9/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"
+    )]
+    #[case(
+        Variant::Standard,
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR x KQkq - 0 1"
+    )]
+    #[case(
+        Variant::Standard,
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkx - 0 1"
+    )]
+    #[case(
+        Variant::Standard,
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq e3 0 1"
+    )]
+    #[case(
+        Variant::Standard,
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1 x"
+    )]
+    #[case(
+        Variant::Standard,
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR[] w KQkq - 0 1"
+    )]
+    #[case(
+        Variant::Standard,
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 3+3 0 1"
+    )]
+    #[case(
+        Variant::Standard,
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQ1BNR w kq - 0 1"
+    )]
+    #[case(Variant::Standard, "4k3/8/8/8/8/8/8/P3K3 w - - 0 1")]
+    #[case(Variant::Standard, "4k3/8/8/8/8/8/4R3/4K3 w - - 0 1")]
+    #[case(Variant::Crazyhouse, "4k3/8/8/8/8/8/8/4K3[K] w - - 0 1")]
+    #[case(Variant::ThreeCheck, "4k3/8/8/8/8/8/8/4K3 w - - 4+3 0 1")]
+    #[case(Variant::Horde, "4k3/8/8/8/8/8/8/4K3 w - - 0 1")]
+    fn parse_fen_err(#[case] variant: Variant, #[case] fen: &str) {
+        assert_eq!(Position::from_fen(variant, fen), Err(ParseError));
+    }
+
+    #[rstest]
+    #[case(Variant::Standard, "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", &[20, 400, 8902])]
+    #[case(Variant::Standard, "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1", &[48, 2039])]
+    #[case(Variant::Standard, "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1", &[14, 191, 2812])]
+    #[case(Variant::Standard, "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8", &[44, 1486])]
+    #[case(Variant::Crazyhouse, "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR[] w KQkq - 0 1", &[20, 400, 8902])]
+    #[case(Variant::Atomic, "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", &[20, 400, 8902])]
+    #[case(Variant::Antichess, "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w - - 0 1", &[20, 400, 8067])]
+    #[case(Variant::Horde, "rnbqkbnr/pppppppp/8/1PP2PP1/PPPPPPPP/PPPPPPPP/PPPPPPPP/PPPPPPPP w kq - 0 1", &[8, 128, 1274])]
+    fn count_leaf_nodes(#[case] variant: Variant, #[case] fen: &str, #[case] expected: &[u64]) {
+        let position = Position::from_fen(variant, fen).unwrap();
+
+        for (depth, &nodes) in (1..).zip(expected) {
+            assert_eq!(position.perft(depth), nodes, "depth {depth}");
+        }
+    }
+
+    #[test]
+    fn reject_illegal_move() {
+        let mut position = Position::new(Variant::Standard);
+        let mv = "e2e5".parse().unwrap();
+
+        assert_eq!(position.play(mv), Err(IllegalMove { mv }));
+        assert_eq!(position, Position::new(Variant::Standard));
+    }
+
+    #[test]
+    fn castle_and_capture_en_passant() {
+        let mut position = Position::from_fen(
+            Variant::Standard,
+            "r3k2r/pppppppp/8/8/8/8/PPPPPPPP/R3K2R w KQkq - 0 1",
+        )
+        .unwrap();
+
+        play(
+            &mut position,
+            &["e1g1", "e8c8", "e2e4", "a7a6", "e4e5", "d7d5", "e5d6"],
+        );
+
+        assert_eq!(
+            position.to_string(),
+            "2kr3r/1pp1pppp/p2P4/8/8/8/PPPP1PPP/R4RK1 b - - 0 4"
+        );
+    }
+
+    #[rstest]
+    #[case(
+        "rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq - 1 3",
+        Some(Outcome::Win(Color::Black))
+    )]
+    #[case("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1", Some(Outcome::Draw))]
+    #[case("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", None)]
+    fn detect_checkmate_and_stalemate(#[case] fen: &str, #[case] expected: Option<Outcome>) {
+        let position = Position::from_fen(Variant::Standard, fen).unwrap();
+        assert_eq!(position.outcome(), expected);
+    }
+
+    #[test]
+    fn drop_captured_pieces() {
+        let position =
+            Position::from_fen(Variant::Crazyhouse, "k7/8/8/8/8/8/8/K7[N] w - - 0 1").unwrap();
+        assert_eq!(position.legal_moves().len(), 3 + 62);
+
+        let mut position =
+            Position::from_fen(Variant::Crazyhouse, "k7/8/8/8/8/1q6/7Q~/KR6[] b - - 0 1").unwrap();
+        play(&mut position, &["b3b1", "a1b1"]);
+
+        assert_eq!(position.to_string(), "k7/8/8/8/8/8/7Q~/1K6[Qr] b - - 0 2");
+        assert!(position.legal_moves().contains(&"R@a2".parse().unwrap()));
+        assert!(!position.legal_moves().contains(&"P@a1".parse().unwrap()));
+
+        play(&mut position, &["R@h1", "h2h1"]);
+        assert_eq!(position.to_string(), "k7/8/8/8/8/8/8/1K5Q~[QR] b - - 0 3");
+    }
+
+    #[test]
+    fn return_promoted_pieces_as_pawns() {
+        let mut position =
+            Position::from_fen(Variant::Crazyhouse, "k6r/8/8/8/8/8/7Q~/1K6[] b - - 0 1").unwrap();
+        play(&mut position, &["h8h2"]);
+
+        assert_eq!(position.to_string(), "k7/8/8/8/8/8/7r/1K6[p] w - - 0 2");
+        assert_eq!(position.pocket(Color::Black).count(PieceKind::Pawn), 1);
+    }
+
+    #[test]
+    fn win_by_third_check() {
+        let mut position = Position::new(Variant::ThreeCheck);
+
+        play(&mut position, &["e2e4", "e7e5", "f1c4", "a7a6", "c4f7"]);
+        assert_eq!(position.remaining_checks(Color::White), 2);
+        assert_eq!(position.outcome(), None);
+
+        play(&mut position, &["e8f7", "d1h5", "f7e7", "h5e5"]);
+        assert_eq!(position.remaining_checks(Color::White), 0);
+        assert_eq!(position.remaining_checks(Color::Black), 3);
+        assert_eq!(position.outcome(), Some(Outcome::Win(Color::White)));
+        assert!(position.legal_moves().is_empty());
+    }
+
+    #[test]
+    fn win_by_king_in_center() {
+        let mut position = Position::new(Variant::KingOfTheHill);
+
+        play(
+            &mut position,
+            &["e2e4", "e7e5", "e1e2", "e8e7", "a2a3", "e7d6"],
+        );
+        play(&mut position, &["a3a4", "d6c5", "a4a5"]);
+        assert_eq!(position.outcome(), None);
+
+        play(&mut position, &["c5d4"]);
+        assert_eq!(position.outcome(), Some(Outcome::Win(Color::Black)));
+    }
+
+    #[test]
+    fn explode_pieces_around_captures() {
+        let mut position = Position::new(Variant::Atomic);
+
+        play(&mut position, &["g1f3", "e7e6", "f3g5", "a7a6", "g5f7"]);
+        assert_eq!(
+            position.to_string(),
+            "rnbq3r/1ppp2pp/p3p3/8/8/8/PPPPPPPP/RNBQKB1R b KQ - 0 3"
+        );
+        assert_eq!(position.outcome(), Some(Outcome::Win(Color::White)));
+        assert!(position.legal_moves().is_empty());
+    }
+
+    #[test]
+    fn forbid_captures_by_kings_in_atomic() {
+        let position =
+            Position::from_fen(Variant::Atomic, "8/8/8/8/8/8/3q4/3K2k1 w - - 0 1").unwrap();
+
+        // Capturing the queen would explode the own king, but kings next to each other are safe
+        assert!(!position.legal_moves().contains(&"d1d2".parse().unwrap()));
+        assert_eq!(position.outcome(), Some(Outcome::Win(Color::Black)));
+
+        let position =
+            Position::from_fen(Variant::Atomic, "8/8/8/8/8/8/8/r1Kk4 w - - 0 1").unwrap();
+        assert!(!position.is_check());
+        assert!(position.legal_moves().contains(&"c1c2".parse().unwrap()));
+        assert!(!position.legal_moves().contains(&"c1b1".parse().unwrap()));
+    }
+
+    #[test]
+    fn force_captures_in_antichess() {
+        let mut position = Position::new(Variant::Antichess);
+
+        play(&mut position, &["e2e3", "b7b5"]);
+        assert_eq!(position.legal_moves(), ["f1b5".parse().unwrap()]);
+
+        let position =
+            Position::from_fen(Variant::Antichess, "8/8/8/8/8/8/8/1q6 w - - 0 1").unwrap();
+        assert_eq!(position.outcome(), Some(Outcome::Win(Color::White)));
+
+        let position =
+            Position::from_fen(Variant::Antichess, "8/1P6/8/8/8/8/8/k7 w - - 0 1").unwrap();
+        assert!(position.legal_moves().contains(&"b7b8k".parse().unwrap()));
+    }
+
+    #[test]
+    fn advance_horde_pawns_from_first_rank() {
+        let position = Position::from_fen(Variant::Horde, "4k3/8/8/8/8/8/8/P7 w - - 0 1").unwrap();
+        let mut moves = position.legal_moves();
+        moves.sort_by_key(ToString::to_string);
+
+        assert_eq!(moves, ["a1a2".parse().unwrap(), "a1a3".parse().unwrap()]);
+
+        let position = Position::from_fen(Variant::Horde, "4k3/8/8/8/8/8/8/8 w - - 0 1").unwrap();
+        assert_eq!(position.outcome(), Some(Outcome::Win(Color::Black)));
+    }
+
+    #[test]
+    fn flip_position() {
+        let mut position = Position::new(Variant::ThreeCheck);
+        play(&mut position, &["e2e4"]);
+
+        assert_eq!(
+            position.flipped().to_string(),
+            "rnbqkbnr/pppp1ppp/8/4p3/8/8/PPPPPPPP/RNBQKBNR w KQkq e6 3+3 0 1"
+        );
+        assert_eq!(position.flipped().flipped(), position);
+    }
+}
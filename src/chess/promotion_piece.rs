@@ -3,7 +3,7 @@ use std::{fmt::Display, str::FromStr};
 use super::ParseError;
 
 /// A piece that a pawn can promote to.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum PromotionPiece {
     /// Promoting to a knight, denoted 'n'.
     Knight,
@@ -16,6 +16,11 @@ pub enum PromotionPiece {
 
     /// Promoting to a queen, denoted 'q'.
     Queen,
+
+    /// Promoting to a king, denoted 'k'.
+    ///
+    /// This is only allowed in antichess, where the king is a regular piece.
+    King,
 }
 
 impl FromStr for PromotionPiece {
@@ -31,6 +36,7 @@ impl FromStr for PromotionPiece {
             "b" => PromotionPiece::Bishop,
             "r" => PromotionPiece::Rook,
             "q" => PromotionPiece::Queen,
+            "k" => PromotionPiece::King,
             _ => return Err(ParseError),
         };
 
@@ -45,6 +51,7 @@ impl Display for PromotionPiece {
             PromotionPiece::Bishop => "b",
             PromotionPiece::Rook => "r",
             PromotionPiece::Queen => "q",
+            PromotionPiece::King => "k",
         };
 
         write!(f, "{piece}")
@@ -61,6 +68,7 @@ mod tests {
     #[case("b", PromotionPiece::Bishop)]
     #[case("r", PromotionPiece::Rook)]
     #[case("q", PromotionPiece::Queen)]
+    #[case("k", PromotionPiece::King)]
     fn parse_promotion_piece_ok(#[case] input: &str, #[case] expected: PromotionPiece) {
         let actual = input.parse();
         assert_eq!(actual, Ok(expected));
@@ -71,14 +79,14 @@ mod tests {
     #[case("B")]
     #[case("R")]
     #[case("Q")]
+    #[case("K")]
     #[case("p")]
-    #[case("k")]
     #[case("")]
     #[case("nn")]
     #[case("br")]
     fn parse_promotion_piece_err(#[case] input: &str) {
         let actual = input.parse::<PromotionPiece>();
-        assert!(matches!(actual, Err(_)));
+        assert!(actual.is_err());
     }
 
     #[rstest]
@@ -86,6 +94,7 @@ mod tests {
     #[case(PromotionPiece::Bishop, "b")]
     #[case(PromotionPiece::Rook, "r")]
     #[case(PromotionPiece::Queen, "q")]
+    #[case(PromotionPiece::King, "k")]
     fn format_promotion_piece(#[case] input: PromotionPiece, #[case] expected: String) {
         let actual = format!("{input}");
         assert_eq!(actual, expected);
@@ -3,7 +3,7 @@ use std::{fmt::Display, str::FromStr};
 use super::ParseError;
 
 /// A rank of the board, denoted from 1 to 8.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Rank {
     /// The first rank of the board, denoted `1`.
     One,
@@ -30,6 +30,39 @@ pub enum Rank {
     Eight,
 }
 
+impl Rank {
+    /// The zero-based index of the rank, starting with Rank::One as `0`.
+    pub const fn index(&self) -> u8 {
+        match self {
+            Rank::One => 0,
+            Rank::Two => 1,
+            Rank::Three => 2,
+            Rank::Four => 3,
+            Rank::Five => 4,
+            Rank::Six => 5,
+            Rank::Seven => 6,
+            Rank::Eight => 7,
+        }
+    }
+
+    /// Get the rank with the given zero-based index, if it is in range.
+    pub const fn from_index(index: u8) -> Option<Self> {
+        let rank = match index {
+            0 => Rank::One,
+            1 => Rank::Two,
+            2 => Rank::Three,
+            3 => Rank::Four,
+            4 => Rank::Five,
+            5 => Rank::Six,
+            6 => Rank::Seven,
+            7 => Rank::Eight,
+            _ => return None,
+        };
+
+        Some(rank)
+    }
+}
+
 impl FromStr for Rank {
     type Err = ParseError;
 
@@ -98,7 +131,7 @@ mod tests {
     #[case("b3")]
     fn parse_rank_err(#[case] input: &str) {
         let actual = input.parse::<Rank>();
-        assert!(matches!(actual, Err(_)));
+        assert!(actual.is_err());
     }
 
     #[rstest]
@@ -1,17 +1,24 @@
-use std::fmt::Display;
+use std::{cmp::Ordering, fmt::Display, ops::Neg, str::FromStr};
+
+use super::{Color, ParseError, Wdl};
 
 /// The current (estimated) evaluation of the game.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Evaluation {
     /// The current player can give checkmate in the given number of moves.
     ///
     /// Note that the number represents moves, not plies.
-    PlayerCheckmate(u8),
+    PlayerCheckmate(u16),
 
     /// The opponent can give checkmate in the given number of moves.
     ///
     /// Note that the number represents moves, not plies.
-    OpponentCheckmate(u8),
+    ///
+    /// `OpponentCheckmate(0)` means that the current player is already checkmated,
+    /// which engines report as `mate 0`.
+    /// Its negation `PlayerCheckmate(0)` is also written as `mate 0`, as UCI has no sign for it,
+    /// but `mate 0` is always read as `OpponentCheckmate(0)`.
+    OpponentCheckmate(u16),
 
     /// The evaluation of the current position in centipawns.
     ///
@@ -20,10 +27,93 @@ pub enum Evaluation {
     Centipawns(i32),
 }
 
+impl Evaluation {
+    /// Convert the evaluation from the point of view of the side to move to White's point of view.
+    ///
+    /// UCI scores are always given from the engine's point of view, i.e. the side to move.
+    pub fn to_white_pov(self, side_to_move: Color) -> Self {
+        match side_to_move {
+            Color::White => self,
+            Color::Black => -self,
+        }
+    }
+
+    /// A key that sorts the evaluations from worst to best for the current player.
+    fn sort_key(&self) -> (u8, i64) {
+        match *self {
+            // Being mated sooner is worse
+            Evaluation::OpponentCheckmate(moves) => (0, moves.into()),
+            Evaluation::Centipawns(cp) => (1, cp.into()),
+            // Mating sooner is better
+            Evaluation::PlayerCheckmate(moves) => (2, -i64::from(moves)),
+        }
+    }
+}
+
+impl Ord for Evaluation {
+    /// Compare the evaluations from the point of view of the current player.
+    ///
+    /// Checkmating the opponent is better than any centipawn evaluation, the shorter the better.
+    /// Getting checkmated is worse than any centipawn evaluation, the sooner the worse.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.sort_key().cmp(&other.sort_key())
+    }
+}
+
+impl PartialOrd for Evaluation {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Neg for Evaluation {
+    type Output = Evaluation;
+
+    /// Flip the point of view of the evaluation to the opponent.
+    fn neg(self) -> Self::Output {
+        match self {
+            Evaluation::PlayerCheckmate(moves) => Evaluation::OpponentCheckmate(moves),
+            Evaluation::OpponentCheckmate(moves) => Evaluation::PlayerCheckmate(moves),
+            Evaluation::Centipawns(cp) => Evaluation::Centipawns(cp.saturating_neg()),
+        }
+    }
+}
+
+impl FromStr for Evaluation {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut tokens = s.split_ascii_whitespace();
+
+        let (Some(unit), Some(value), None) = (tokens.next(), tokens.next(), tokens.next()) else {
+            return Err(ParseError);
+        };
+
+        let eval = match unit {
+            "cp" => Evaluation::Centipawns(value.parse().map_err(|_| ParseError)?),
+            "mate" => {
+                let moves = value.parse::<i32>().map_err(|_| ParseError)?;
+                let abs_moves = u16::try_from(moves.unsigned_abs()).map_err(|_| ParseError)?;
+
+                if moves > 0 {
+                    Evaluation::PlayerCheckmate(abs_moves)
+                } else {
+                    Evaluation::OpponentCheckmate(abs_moves)
+                }
+            }
+            _ => return Err(ParseError),
+        };
+
+        Ok(eval)
+    }
+}
+
 impl Display for Evaluation {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let output = match self {
+            // `mate 0` has no sign, whoever is checkmated
             Evaluation::PlayerCheckmate(moves) => format!("mate {moves}"),
+            Evaluation::OpponentCheckmate(0) => "mate 0".to_string(),
             Evaluation::OpponentCheckmate(moves) => format!("mate -{moves}"),
             Evaluation::Centipawns(cp) => format!("cp {cp}"),
         };
@@ -33,7 +123,7 @@ impl Display for Evaluation {
 }
 
 /// The current game score.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Score {
     /// The evaluation of the position.
     eval: Evaluation,
@@ -43,6 +133,9 @@ pub struct Score {
 
     /// Indicates whether the score is just an upper bound.
     is_upper_bound: bool,
+
+    /// The expected win, draw and loss chances, if the engine reports them.
+    wdl: Option<Wdl>,
 }
 
 impl Score {
@@ -52,6 +145,7 @@ impl Score {
             eval,
             is_lower_bound: false,
             is_upper_bound: false,
+            wdl: None,
         }
     }
 
@@ -61,6 +155,7 @@ impl Score {
             eval,
             is_lower_bound: true,
             is_upper_bound: false,
+            wdl: None,
         }
     }
 
@@ -70,14 +165,147 @@ impl Score {
             eval,
             is_lower_bound: false,
             is_upper_bound: true,
+            wdl: None,
+        }
+    }
+
+    /// Set the expected win, draw and loss chances.
+    ///
+    /// The engine should only send this if the option `UCI_ShowWDL` is set to `true`.
+    pub fn with_wdl(mut self, wdl: Wdl) -> Self {
+        self.wdl = Some(wdl);
+        self
+    }
+
+    /// The evaluation of the position.
+    pub fn eval(&self) -> Evaluation {
+        self.eval
+    }
+
+    /// The expected win, draw and loss chances, if the engine reports them.
+    pub fn wdl(&self) -> Option<Wdl> {
+        self.wdl
+    }
+
+    /// Determines if the score is just a lower bound.
+    pub fn is_lower_bound(&self) -> bool {
+        self.is_lower_bound
+    }
+
+    /// Determines if the score is just an upper bound.
+    pub fn is_upper_bound(&self) -> bool {
+        self.is_upper_bound
+    }
+
+    /// Convert the score from the point of view of the side to move to White's point of view.
+    ///
+    /// If the point of view is flipped, lower bounds become upper bounds and vice versa.
+    pub fn to_white_pov(self, side_to_move: Color) -> Self {
+        match side_to_move {
+            Color::White => self,
+            Color::Black => -self,
+        }
+    }
+
+    /// Compare two scores, taking their bounds into account.
+    ///
+    /// Returns `None` if the bounds don't allow to decide which score is better,
+    /// e.g. for a lower bound of 30 centipawns and an exact score of 50 centipawns.
+    pub fn compare(&self, other: &Score) -> Option<Ordering> {
+        if let (Some(min), Some(other_max)) = (self.min(), other.max()) {
+            if min > other_max {
+                return Some(Ordering::Greater);
+            }
+        }
+
+        if let (Some(max), Some(other_min)) = (self.max(), other.min()) {
+            if max < other_min {
+                return Some(Ordering::Less);
+            }
+        }
+
+        if self.is_exact() && other.is_exact() && self.eval == other.eval {
+            return Some(Ordering::Equal);
+        }
+
+        None
+    }
+
+    /// Determines if the score is neither a lower nor an upper bound.
+    fn is_exact(&self) -> bool {
+        !self.is_lower_bound && !self.is_upper_bound
+    }
+
+    /// The smallest evaluation that the score can represent, `None` if unbounded.
+    fn min(&self) -> Option<Evaluation> {
+        (!self.is_upper_bound).then_some(self.eval)
+    }
+
+    /// The biggest evaluation that the score can represent, `None` if unbounded.
+    fn max(&self) -> Option<Evaluation> {
+        (!self.is_lower_bound).then_some(self.eval)
+    }
+}
+
+impl Neg for Score {
+    type Output = Score;
+
+    /// Flip the point of view of the score to the opponent.
+    ///
+    /// Lower bounds become upper bounds and vice versa.
+    fn neg(self) -> Self::Output {
+        Score {
+            eval: -self.eval,
+            is_lower_bound: self.is_upper_bound,
+            is_upper_bound: self.is_lower_bound,
+            wdl: self.wdl.map(Neg::neg),
         }
     }
 }
 
+impl FromStr for Score {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens: Vec<_> = s.split_ascii_whitespace().collect();
+
+        let ["score", unit, value, rest @ ..] = tokens.as_slice() else {
+            return Err(ParseError);
+        };
+        let mut rest = rest;
+
+        let mut score = Score::new(format!("{unit} {value}").parse::<Evaluation>()?);
+
+        while !rest.is_empty() {
+            rest = match rest {
+                ["lowerbound", rest @ ..] if score.is_exact() => {
+                    score.is_lower_bound = true;
+                    rest
+                }
+                ["upperbound", rest @ ..] if score.is_exact() => {
+                    score.is_upper_bound = true;
+                    rest
+                }
+                ["wdl", win, draw, loss, rest @ ..] if score.wdl.is_none() => {
+                    score.wdl = Some(format!("wdl {win} {draw} {loss}").parse()?);
+                    rest
+                }
+                _ => return Err(ParseError),
+            };
+        }
+
+        Ok(score)
+    }
+}
+
 impl Display for Score {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut output = format!("score {}", self.eval);
 
+        if let Some(wdl) = &self.wdl {
+            output += &format!(" {wdl}");
+        }
+
         if self.is_lower_bound {
             output += " lowerbound";
         }
@@ -100,6 +328,8 @@ mod tests {
     #[case(Score::new(Evaluation::OpponentCheckmate(4)), "score mate -4")]
     #[case(Score::new(Evaluation::Centipawns(12)), "score cp 12")]
     #[case(Score::new(Evaluation::Centipawns(-60)), "score cp -60")]
+    #[case(Score::new(Evaluation::PlayerCheckmate(300)), "score mate 300")]
+    #[case(Score::new(Evaluation::OpponentCheckmate(0)), "score mate 0")]
     #[case(
         Score::lower_bound(Evaluation::Centipawns(35)),
         "score cp 35 lowerbound"
@@ -108,8 +338,209 @@ mod tests {
         Score::uppper_bound(Evaluation::Centipawns(87)),
         "score cp 87 upperbound"
     )]
+    #[case(
+        Score::new(Evaluation::Centipawns(35)).with_wdl(Wdl::new(412, 531, 57)),
+        "score cp 35 wdl 412 531 57"
+    )]
+    #[case(
+        Score::lower_bound(Evaluation::PlayerCheckmate(4)).with_wdl(Wdl::new(1000, 0, 0)),
+        "score mate 4 wdl 1000 0 0 lowerbound"
+    )]
     fn format_score(#[case] input: Score, #[case] expected: String) {
         let actual = format!("{input}");
         assert_eq!(actual, expected);
     }
+
+    #[rstest]
+    #[case("score mate 2", Score::new(Evaluation::PlayerCheckmate(2)))]
+    #[case("score mate -4", Score::new(Evaluation::OpponentCheckmate(4)))]
+    #[case("score mate 300", Score::new(Evaluation::PlayerCheckmate(300)))]
+    #[case("score mate 0", Score::new(Evaluation::OpponentCheckmate(0)))]
+    #[case("score mate -0", Score::new(Evaluation::OpponentCheckmate(0)))]
+    #[case("score cp -60", Score::new(Evaluation::Centipawns(-60)))]
+    #[case(
+        "score cp 35 lowerbound",
+        Score::lower_bound(Evaluation::Centipawns(35))
+    )]
+    #[case(
+        "score cp 87 upperbound",
+        Score::uppper_bound(Evaluation::Centipawns(87))
+    )]
+    #[case(
+        "score cp 35 wdl 412 531 57",
+        Score::new(Evaluation::Centipawns(35)).with_wdl(Wdl::new(412, 531, 57))
+    )]
+    #[case(
+        "score cp 35 wdl 412 531 57 upperbound",
+        Score::uppper_bound(Evaluation::Centipawns(35)).with_wdl(Wdl::new(412, 531, 57))
+    )]
+    #[case(
+        "score cp 35 upperbound wdl 412 531 57",
+        Score::uppper_bound(Evaluation::Centipawns(35)).with_wdl(Wdl::new(412, 531, 57))
+    )]
+    fn parse_score_ok(#[case] input: &str, #[case] expected: Score) {
+        let actual = input.parse();
+        assert_eq!(actual, Ok(expected));
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("score")]
+    #[case("cp 12")]
+    #[case("score cp")]
+    #[case("score cp 1.5")]
+    #[case("score mate 70000")]
+    #[case("score pawns 1")]
+    #[case("score cp 35 lowerbound upperbound")]
+    #[case("score cp 35 lowerbound lowerbound")]
+    #[case("score cp 35 wdl 1 2")]
+    #[case("score cp 35 wdl 1 2 3 wdl 1 2 3")]
+    fn parse_score_err(#[case] input: &str) {
+        let actual = input.parse::<Score>();
+        assert!(actual.is_err());
+    }
+
+    #[test]
+    fn sort_evaluations() {
+        let mut actual = vec![
+            Evaluation::Centipawns(30),
+            Evaluation::OpponentCheckmate(5),
+            Evaluation::PlayerCheckmate(3),
+            Evaluation::Centipawns(-200),
+            Evaluation::OpponentCheckmate(0),
+            Evaluation::PlayerCheckmate(1),
+            Evaluation::Centipawns(i32::MAX),
+            Evaluation::OpponentCheckmate(1),
+        ];
+        actual.sort();
+
+        let expected = vec![
+            Evaluation::OpponentCheckmate(0),
+            Evaluation::OpponentCheckmate(1),
+            Evaluation::OpponentCheckmate(5),
+            Evaluation::Centipawns(-200),
+            Evaluation::Centipawns(30),
+            Evaluation::Centipawns(i32::MAX),
+            Evaluation::PlayerCheckmate(3),
+            Evaluation::PlayerCheckmate(1),
+        ];
+
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case(Evaluation::PlayerCheckmate(2), Evaluation::OpponentCheckmate(2))]
+    #[case(Evaluation::OpponentCheckmate(0), Evaluation::PlayerCheckmate(0))]
+    #[case(Evaluation::PlayerCheckmate(0), Evaluation::OpponentCheckmate(0))]
+    #[case(Evaluation::Centipawns(35), Evaluation::Centipawns(-35))]
+    #[case(Evaluation::Centipawns(i32::MIN), Evaluation::Centipawns(i32::MAX))]
+    fn negate_evaluation(#[case] input: Evaluation, #[case] expected: Evaluation) {
+        assert_eq!(-input, expected);
+    }
+
+    #[rstest]
+    #[case(Evaluation::OpponentCheckmate(0))]
+    #[case(Evaluation::PlayerCheckmate(0))]
+    #[case(Evaluation::PlayerCheckmate(3))]
+    #[case(Evaluation::OpponentCheckmate(3))]
+    #[case(Evaluation::Centipawns(-20))]
+    #[case(Evaluation::Centipawns(i32::MAX))]
+    fn negate_evaluation_twice(#[case] input: Evaluation) {
+        assert_eq!(-(-input), input);
+        assert_eq!(
+            input.to_white_pov(Color::Black).to_white_pov(Color::Black),
+            input
+        );
+    }
+
+    #[rstest]
+    #[case(Evaluation::OpponentCheckmate(3), Evaluation::PlayerCheckmate(0))]
+    #[case(Evaluation::Centipawns(i32::MAX), Evaluation::PlayerCheckmate(0))]
+    #[case(Evaluation::Centipawns(-20), Evaluation::PlayerCheckmate(1))]
+    fn negation_reverses_order(#[case] worse: Evaluation, #[case] better: Evaluation) {
+        assert!(worse < better);
+        assert!(-worse > -better);
+    }
+
+    #[rstest]
+    #[case(Color::White, Evaluation::OpponentCheckmate(0))]
+    #[case(Color::Black, Evaluation::PlayerCheckmate(0))]
+    fn convert_mate_0_to_white_pov(#[case] side_to_move: Color, #[case] expected: Evaluation) {
+        assert_eq!(
+            Evaluation::OpponentCheckmate(0).to_white_pov(side_to_move),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case(Score::new(Evaluation::OpponentCheckmate(0)))]
+    #[case(Score::new(Evaluation::PlayerCheckmate(3)))]
+    #[case(Score::new(Evaluation::OpponentCheckmate(3)))]
+    #[case(Score::lower_bound(Evaluation::Centipawns(-20)).with_wdl(Wdl::new(10, 600, 390)))]
+    fn negated_score_round_trips(#[case] input: Score) {
+        let negated = -input;
+        let parsed: Score = negated.to_string().parse().unwrap();
+
+        // `mate 0` loses its sign
+        if negated.eval == Evaluation::PlayerCheckmate(0) {
+            assert_eq!(
+                parsed,
+                Score {
+                    eval: Evaluation::OpponentCheckmate(0),
+                    ..negated
+                }
+            );
+        } else {
+            assert_eq!(parsed, negated);
+        }
+
+        assert_eq!(-negated, input);
+    }
+
+    #[rstest]
+    #[case(Score::new(Evaluation::Centipawns(35)), Color::White, "score cp 35")]
+    #[case(Score::new(Evaluation::Centipawns(35)), Color::Black, "score cp -35")]
+    #[case(
+        Score::lower_bound(Evaluation::PlayerCheckmate(3)),
+        Color::Black,
+        "score mate -3 upperbound"
+    )]
+    #[case(
+        Score::uppper_bound(Evaluation::Centipawns(-10)),
+        Color::Black,
+        "score cp 10 lowerbound"
+    )]
+    #[case(
+        Score::new(Evaluation::Centipawns(-40)).with_wdl(Wdl::new(50, 600, 350)),
+        Color::Black,
+        "score cp 40 wdl 350 600 50"
+    )]
+    fn score_to_white_pov(
+        #[case] input: Score,
+        #[case] side_to_move: Color,
+        #[case] expected: String,
+    ) {
+        let actual = input.to_white_pov(side_to_move).to_string();
+        assert_eq!(actual, expected);
+    }
+
+    #[rstest]
+    #[case("score cp 50", "score cp 30", Some(Ordering::Greater))]
+    #[case("score cp 30", "score cp 30", Some(Ordering::Equal))]
+    #[case("score cp 30 lowerbound", "score cp 20", Some(Ordering::Greater))]
+    #[case("score cp 30 lowerbound", "score cp 50", None)]
+    #[case("score cp 30 lowerbound", "score cp 30", None)]
+    #[case("score cp 30 upperbound", "score cp 50", Some(Ordering::Less))]
+    #[case(
+        "score cp 30 upperbound",
+        "score cp 50 lowerbound",
+        Some(Ordering::Less)
+    )]
+    #[case("score cp 30 upperbound", "score cp 20 upperbound", None)]
+    #[case("score cp 30 lowerbound", "score cp 50 lowerbound", None)]
+    #[case("score mate 3 lowerbound", "score cp 900", Some(Ordering::Greater))]
+    fn compare_scores(#[case] a: Score, #[case] b: Score, #[case] expected: Option<Ordering>) {
+        assert_eq!(a.compare(&b), expected);
+        assert_eq!(b.compare(&a), expected.map(Ordering::reverse));
+    }
 }
@@ -3,7 +3,7 @@ use std::{fmt::Display, str::FromStr};
 use super::{File, ParseError, Rank};
 
 /// A square on the chess board.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Square {
     /// The file that the square is on.
     pub file: File,
@@ -17,6 +17,19 @@ impl Square {
     pub const fn new(file: File, rank: Rank) -> Self {
         Self { file, rank }
     }
+
+    /// The zero-based index of the square, from `a1` as `0` to `h8` as `63`.
+    pub const fn index(&self) -> u8 {
+        self.rank.index() * 8 + self.file.index()
+    }
+
+    /// Get the square with the given zero-based index, if it is in range.
+    pub const fn from_index(index: u8) -> Option<Self> {
+        match (File::from_index(index % 8), Rank::from_index(index / 8)) {
+            (Some(file), Some(rank)) => Some(Square::new(file, rank)),
+            _ => None,
+        }
+    }
 }
 
 impl FromStr for Square {
@@ -27,8 +40,8 @@ impl FromStr for Square {
             return Err(ParseError);
         }
 
-        let file = s[(0..1)].parse::<File>()?;
-        let rank = s[(1..2)].parse::<Rank>()?;
+        let file = s[0..1].parse::<File>()?;
+        let rank = s[1..2].parse::<Rank>()?;
 
         Ok(Square::new(file, rank))
     }
@@ -63,7 +76,7 @@ mod tests {
     #[case("a3b5")]
     fn parse_square_err(#[case] input: &str) {
         let actual = input.parse::<Square>();
-        assert!(matches!(actual, Err(_)));
+        assert!(actual.is_err());
     }
 
     #[rstest]
@@ -75,4 +88,19 @@ mod tests {
         let actual = format!("{input}");
         assert_eq!(actual, expected);
     }
+
+    #[rstest]
+    #[case(Square::new(File::A, Rank::One), 0)]
+    #[case(Square::new(File::H, Rank::One), 7)]
+    #[case(Square::new(File::C, Rank::Two), 10)]
+    #[case(Square::new(File::H, Rank::Eight), 63)]
+    fn square_index(#[case] square: Square, #[case] index: u8) {
+        assert_eq!(square.index(), index);
+        assert_eq!(Square::from_index(index), Some(square));
+    }
+
+    #[test]
+    fn square_from_index_out_of_range() {
+        assert_eq!(Square::from_index(64), None);
+    }
 }
@@ -0,0 +1,152 @@
+use std::{fmt::Display, str::FromStr};
+
+use super::ParseError;
+
+/// A chess variant, as selected by the `UCI_Variant` option.
+///
+/// The names follow the conventions of multi-variant engines such as Fairy-Stockfish.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Variant {
+    /// Standard chess, denoted `chess`.
+    #[default]
+    Standard,
+
+    /// Captured pieces can be dropped back onto the board, denoted `crazyhouse`.
+    Crazyhouse,
+
+    /// The first player to give check three times wins, denoted `3check`.
+    ThreeCheck,
+
+    /// Bringing the king to one of the four center squares wins, denoted `kingofthehill`.
+    KingOfTheHill,
+
+    /// Captures explode and remove all surrounding non-pawn pieces, denoted `atomic`.
+    Atomic,
+
+    /// Captures are compulsory and the player who loses all pieces wins, denoted `antichess`.
+    Antichess,
+
+    /// White starts with a horde of pawns instead of regular pieces, denoted `horde`.
+    Horde,
+}
+
+impl Variant {
+    /// All supported variants, starting with standard chess.
+    pub const ALL: [Variant; 7] = [
+        Variant::Standard,
+        Variant::Crazyhouse,
+        Variant::ThreeCheck,
+        Variant::KingOfTheHill,
+        Variant::Atomic,
+        Variant::Antichess,
+        Variant::Horde,
+    ];
+
+    /// Determines if pieces can be dropped onto the board in this variant.
+    ///
+    /// The FEN of such a variant contains the pockets of both players.
+    pub fn has_drops(&self) -> bool {
+        matches!(self, Variant::Crazyhouse)
+    }
+
+    /// Determines if the number of given checks is relevant in this variant.
+    ///
+    /// The FEN of such a variant contains the remaining checks of both players.
+    pub fn has_check_counters(&self) -> bool {
+        matches!(self, Variant::ThreeCheck)
+    }
+
+    /// The FEN of the start position of this variant.
+    pub fn start_fen(&self) -> &'static str {
+        match self {
+            Variant::Standard | Variant::KingOfTheHill | Variant::Atomic => {
+                "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"
+            }
+            Variant::Crazyhouse => "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR[] w KQkq - 0 1",
+            Variant::ThreeCheck => "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 3+3 0 1",
+            Variant::Antichess => "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w - - 0 1",
+            Variant::Horde => {
+                "rnbqkbnr/pppppppp/8/1PP2PP1/PPPPPPPP/PPPPPPPP/PPPPPPPP/PPPPPPPP w kq - 0 1"
+            }
+        }
+    }
+}
+
+impl FromStr for Variant {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let variant = match s {
+            "chess" | "standard" => Variant::Standard,
+            "crazyhouse" => Variant::Crazyhouse,
+            "3check" | "threecheck" => Variant::ThreeCheck,
+            "kingofthehill" => Variant::KingOfTheHill,
+            "atomic" => Variant::Atomic,
+            "antichess" | "giveaway" => Variant::Antichess,
+            "horde" => Variant::Horde,
+            _ => return Err(ParseError),
+        };
+
+        Ok(variant)
+    }
+}
+
+impl Display for Variant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let variant = match self {
+            Variant::Standard => "chess",
+            Variant::Crazyhouse => "crazyhouse",
+            Variant::ThreeCheck => "3check",
+            Variant::KingOfTheHill => "kingofthehill",
+            Variant::Atomic => "atomic",
+            Variant::Antichess => "antichess",
+            Variant::Horde => "horde",
+        };
+
+        write!(f, "{variant}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("chess", Variant::Standard)]
+    #[case("standard", Variant::Standard)]
+    #[case("crazyhouse", Variant::Crazyhouse)]
+    #[case("3check", Variant::ThreeCheck)]
+    #[case("kingofthehill", Variant::KingOfTheHill)]
+    #[case("atomic", Variant::Atomic)]
+    #[case("antichess", Variant::Antichess)]
+    #[case("giveaway", Variant::Antichess)]
+    #[case("horde", Variant::Horde)]
+    fn parse_variant_ok(#[case] input: &str, #[case] expected: Variant) {
+        let actual = input.parse();
+        assert_eq!(actual, Ok(expected));
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("Chess")]
+    #[case("fischerandom")]
+    #[case("crazy house")]
+    fn parse_variant_err(#[case] input: &str) {
+        let actual = input.parse::<Variant>();
+        assert!(actual.is_err());
+    }
+
+    #[rstest]
+    #[case(Variant::Standard, "chess")]
+    #[case(Variant::Crazyhouse, "crazyhouse")]
+    #[case(Variant::ThreeCheck, "3check")]
+    #[case(Variant::KingOfTheHill, "kingofthehill")]
+    #[case(Variant::Atomic, "atomic")]
+    #[case(Variant::Antichess, "antichess")]
+    #[case(Variant::Horde, "horde")]
+    fn format_variant(#[case] input: Variant, #[case] expected: String) {
+        let actual = format!("{input}");
+        assert_eq!(actual, expected);
+    }
+}
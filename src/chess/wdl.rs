@@ -0,0 +1,255 @@
+use std::{fmt::Display, ops::Neg, str::FromStr};
+
+use super::{Evaluation, ParseError};
+
+/// The expected win, draw and loss chances of the current player, in permill.
+///
+/// Engines send this as `wdl <win> <draw> <loss>` if the option `UCI_ShowWDL` is set to `true`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Wdl {
+    /// The chance to win the game, in permill.
+    pub win: u16,
+
+    /// The chance to draw the game, in permill.
+    pub draw: u16,
+
+    /// The chance to lose the game, in permill.
+    pub loss: u16,
+}
+
+impl Wdl {
+    /// Create new win, draw and loss chances, in permill.
+    pub const fn new(win: u16, draw: u16, loss: u16) -> Self {
+        Self { win, draw, loss }
+    }
+
+    /// The expected score of the game for the current player, between `0.0` and `1.0`.
+    ///
+    /// A win counts as `1.0`, a draw as `0.5` and a loss as `0.0`.
+    pub fn expected_score(&self) -> f64 {
+        let total = f64::from(self.win) + f64::from(self.draw) + f64::from(self.loss);
+
+        if total == 0.0 {
+            return 0.5;
+        }
+
+        (f64::from(self.win) + f64::from(self.draw) / 2.0) / total
+    }
+}
+
+impl Neg for Wdl {
+    type Output = Wdl;
+
+    /// Flip the point of view to the opponent.
+    fn neg(self) -> Self::Output {
+        Wdl::new(self.loss, self.draw, self.win)
+    }
+}
+
+impl FromStr for Wdl {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens: Vec<_> = s.split_ascii_whitespace().collect();
+
+        let ["wdl", win, draw, loss] = tokens.as_slice() else {
+            return Err(ParseError);
+        };
+
+        let parse = |value: &str| value.parse::<u16>().map_err(|_| ParseError);
+
+        Ok(Wdl::new(parse(win)?, parse(draw)?, parse(loss)?))
+    }
+}
+
+impl Display for Wdl {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "wdl {} {} {}", self.win, self.draw, self.loss)
+    }
+}
+
+/// A logistic model to estimate win, draw and loss chances from centipawn evaluations.
+///
+/// The chance to win is `1 / (1 + exp((a - cp) / b))` and the chance to lose is
+/// `1 / (1 + exp((a + cp) / b))`.
+/// `a` and `b` are cubic polynomials in the game phase `x`, which is either the game ply
+/// or the material on the board, depending on the model.
+/// Before evaluating the polynomials, `x` is clamped to `input_range` and divided by `input_scale`.
+///
+/// This allows to normalize engines without WDL output onto the same expected score scale.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WdlModel {
+    /// The coefficients of `a`, the centipawns for a 50% chance to win, highest degree first.
+    pub a: [f64; 4],
+
+    /// The coefficients of `b`, the spread of the logistic curve, highest degree first.
+    pub b: [f64; 4],
+
+    /// The minimum and maximum value of the game phase input.
+    pub input_range: (f64, f64),
+
+    /// The value that the clamped game phase input is divided by.
+    pub input_scale: f64,
+}
+
+impl WdlModel {
+    /// A model that does not depend on the game phase.
+    pub const fn constant(a: f64, b: f64) -> Self {
+        Self {
+            a: [0.0, 0.0, 0.0, a],
+            b: [0.0, 0.0, 0.0, b],
+            input_range: (0.0, 0.0),
+            input_scale: 1.0,
+        }
+    }
+
+    /// A model depending on the game ply, as fitted by Stockfish 16 and rescaled to centipawns.
+    ///
+    /// An evaluation of 100 centipawns at ply 64 corresponds to a 50% chance to win.
+    pub const fn by_ply() -> Self {
+        Self {
+            a: [0.11596502, -0.85980204, 7.06671383, 93.70965978],
+            b: [-0.69949614, 4.04783472, -4.35008812, 19.34548271],
+            input_range: (0.0, 240.0),
+            input_scale: 64.0,
+        }
+    }
+
+    /// A model depending on the material on the board, as fitted by Stockfish 16.1 and rescaled
+    /// to centipawns.
+    ///
+    /// The material is counted as 1 per pawn, 3 per knight and bishop, 5 per rook and 9 per queen.
+    /// An evaluation of 100 centipawns with 58 material corresponds to a 50% chance to win.
+    pub const fn by_material() -> Self {
+        Self {
+            a: [-52.16844237, 141.81183816, -123.19745995, 133.15900176],
+            b: [25.06613126, -38.48916094, 20.58614894, 13.35218031],
+            input_range: (17.0, 78.0),
+            input_scale: 58.0,
+        }
+    }
+
+    /// The chance to win with the given centipawn evaluation, between `0.0` and `1.0`.
+    ///
+    /// `input` is the game ply or the material, depending on the model.
+    pub fn win_probability(&self, cp: i32, input: f64) -> f64 {
+        let x = input.clamp(self.input_range.0, self.input_range.1) / self.input_scale;
+        let polynomial = |c: [f64; 4]| ((c[0] * x + c[1]) * x + c[2]) * x + c[3];
+
+        let a = polynomial(self.a);
+        let b = polynomial(self.b);
+
+        1.0 / (1.0 + ((a - f64::from(cp)) / b).exp())
+    }
+
+    /// Estimate the win, draw and loss chances for the given evaluation.
+    ///
+    /// `input` is the game ply or the material, depending on the model.
+    pub fn wdl(&self, eval: Evaluation, input: f64) -> Wdl {
+        match eval {
+            Evaluation::PlayerCheckmate(_) => Wdl::new(1000, 0, 0),
+            Evaluation::OpponentCheckmate(_) => Wdl::new(0, 0, 1000),
+            Evaluation::Centipawns(cp) => {
+                let win = (self.win_probability(cp, input) * 1000.0).round() as u16;
+                let loss =
+                    (self.win_probability(cp.saturating_neg(), input) * 1000.0).round() as u16;
+                let loss = loss.min(1000 - win);
+
+                Wdl::new(win, 1000 - win - loss, loss)
+            }
+        }
+    }
+
+    /// The expected score for the given evaluation, between `0.0` and `1.0`.
+    ///
+    /// `input` is the game ply or the material, depending on the model.
+    pub fn expected_score(&self, eval: Evaluation, input: f64) -> f64 {
+        self.wdl(eval, input).expected_score()
+    }
+}
+
+impl Default for WdlModel {
+    fn default() -> Self {
+        Self::by_ply()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("wdl 412 531 57", Wdl::new(412, 531, 57))]
+    #[case("wdl 0 1000 0", Wdl::new(0, 1000, 0))]
+    fn parse_wdl_ok(#[case] input: &str, #[case] expected: Wdl) {
+        let actual = input.parse();
+        assert_eq!(actual, Ok(expected));
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("wdl 1 2")]
+    #[case("wdl 1 2 3 4")]
+    #[case("wdl -1 2 3")]
+    #[case("dlw 1 2 3")]
+    fn parse_wdl_err(#[case] input: &str) {
+        let actual = input.parse::<Wdl>();
+        assert!(actual.is_err());
+    }
+
+    #[test]
+    fn format_wdl() {
+        assert_eq!(Wdl::new(412, 531, 57).to_string(), "wdl 412 531 57");
+    }
+
+    #[rstest]
+    #[case(Wdl::new(1000, 0, 0), 1.0)]
+    #[case(Wdl::new(0, 1000, 0), 0.5)]
+    #[case(Wdl::new(0, 0, 1000), 0.0)]
+    #[case(Wdl::new(400, 500, 100), 0.65)]
+    fn wdl_expected_score(#[case] wdl: Wdl, #[case] expected: f64) {
+        assert!((wdl.expected_score() - expected).abs() < 1e-9);
+    }
+
+    #[rstest]
+    #[case(WdlModel::by_ply(), 64.0)]
+    #[case(WdlModel::by_material(), 58.0)]
+    fn model_is_normalized_to_pawn(#[case] model: WdlModel, #[case] input: f64) {
+        assert!((model.win_probability(100, input) - 0.5).abs() < 0.01);
+    }
+
+    #[rstest]
+    #[case(WdlModel::by_ply(), 30.0)]
+    #[case(WdlModel::by_material(), 40.0)]
+    #[case(WdlModel::constant(100.0, 50.0), 0.0)]
+    fn model_wdl_is_consistent(#[case] model: WdlModel, #[case] input: f64) {
+        let mut last_expected_score = 0.0;
+
+        for cp in (-1000..=1000).step_by(50) {
+            let wdl = model.wdl(Evaluation::Centipawns(cp), input);
+            assert_eq!(wdl.win + wdl.draw + wdl.loss, 1000);
+
+            let expected_score = wdl.expected_score();
+            assert!(expected_score >= last_expected_score);
+            last_expected_score = expected_score;
+        }
+
+        let equal = model.wdl(Evaluation::Centipawns(0), input);
+        assert_eq!(equal.win, equal.loss);
+    }
+
+    #[test]
+    fn model_wdl_for_mates() {
+        let model = WdlModel::default();
+
+        assert_eq!(
+            model.wdl(Evaluation::PlayerCheckmate(3), 10.0),
+            Wdl::new(1000, 0, 0)
+        );
+        assert_eq!(
+            model.wdl(Evaluation::OpponentCheckmate(0), 10.0),
+            Wdl::new(0, 0, 1000)
+        );
+    }
+}
@@ -3,7 +3,7 @@ use std::fmt::Display;
 use crate::chess::Move;
 
 /// The engine has stopped searching and found the given move best in this position.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct BestMoveCommand {
     /// The best move in this position, according to the engine.
     pub best_move: Move,
@@ -47,7 +47,7 @@ impl Display for BestMoveCommand {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::chess::{File, Move, Rank, Square};
+    use crate::chess::{DropPiece, File, Move, Rank, Square};
     use rstest::rstest;
 
     #[rstest]
@@ -65,6 +65,13 @@ mod tests {
         ),
         "bestmove g1f3 ponder d8f6"
     )]
+    #[case(
+        BestMoveCommand::new_with_ponder(
+            Move::new_drop(DropPiece::Pawn, Square::new(File::E, Rank::Four)),
+            Move::new_drop(DropPiece::Bishop, Square::new(File::C, Rank::Five))
+        ),
+        "bestmove P@e4 ponder B@c5"
+    )]
     fn format_best_move_cmd(#[case] input: BestMoveCommand, #[case] expected: String) {
         let actual = format!("{input}");
         assert_eq!(actual, expected);
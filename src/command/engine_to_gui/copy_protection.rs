@@ -1,6 +1,7 @@
 use std::fmt::Display;
 
 /// A command for copy protected engines to communicate the status to the GUI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum CopyProtectionCommand {
     /// Tell the GUI that the copy protection is being checked now.
     Checking,
@@ -1,7 +1,7 @@
 use std::fmt::Display;
 
 /// Command to identify the engine to the GUI.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum IdCommand {
     /// The name of the engine.
     Name(String),
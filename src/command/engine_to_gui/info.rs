@@ -3,30 +3,30 @@ use std::{fmt::Display, time::Duration};
 use crate::chess::{Move, MoveLine, Score};
 
 /// A command sending information from the engine to the GUI.
-#[derive(Debug, Default, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub struct InfoCommand {
     /// Search depth in plies.
-    depth_plies: Option<usize>,
+    depth_plies: Option<u32>,
 
     /// Selective search depth in plies.
     ///
     /// If the engine sends `seldepth` there must also be a `depth` present in the same string.
-    sel_depth_plies: Option<usize>,
+    sel_depth_plies: Option<u32>,
 
     /// The score from the engine's point of view.
     score: Option<Score>,
 
     /// The number of nodes that the engine searched.
-    node_count: Option<usize>,
+    node_count: Option<u64>,
 
     /// The number of nodes that the engine searched per second.
-    nodes_per_second: Option<usize>,
+    nodes_per_second: Option<u64>,
 
     /// The number of positions found in the endgame table bases.
-    endgame_tb_hits: Option<usize>,
+    endgame_tb_hits: Option<u64>,
 
     /// The number of positions found in the shredder endgame databases.
-    shredder_tb_hits: Option<usize>,
+    shredder_tb_hits: Option<u64>,
 
     /// The time searched.
     time: Option<Duration>,
@@ -34,13 +34,13 @@ pub struct InfoCommand {
     /// The best line found.
     ///
     /// The first number represents the k-th best line.
-    pv: Option<(usize, MoveLine)>,
+    pv: Option<(u32, MoveLine)>,
 
     /// The CPU load of the engine in permill.
-    cpu_load_permill: Option<usize>,
+    cpu_load_permill: Option<u16>,
 
     /// The hash table fill in permill.
-    hash_full_permill: Option<usize>,
+    hash_full_permill: Option<u16>,
 
     /// The currently searched move.
     curr_move: Option<Move>,
@@ -48,7 +48,7 @@ pub struct InfoCommand {
     /// The number of the currently searched move.
     ///
     /// Should be `1` for the first move, not `0`.
-    curr_move_number: Option<usize>,
+    curr_move_number: Option<u32>,
 
     /// The given move is refuted by the given line.
     ///
@@ -60,7 +60,7 @@ pub struct InfoCommand {
     /// The first paramater represents the CPU number.
     ///
     /// The engine should only send this if the option `UCI_ShowCurrLine` is set to `true`.
-    curr_line: Option<(usize, MoveLine)>,
+    curr_line: Option<(u32, MoveLine)>,
 
     /// Any string string which will be displayed by the engine,
     string: Option<String>,
@@ -75,7 +75,7 @@ impl InfoCommand {
     }
 
     /// Set the search depth (in plies).
-    pub fn with_depth_plies(mut self, depth_plies: usize) -> Self {
+    pub fn with_depth_plies(mut self, depth_plies: u32) -> Self {
         self.depth_plies = Some(depth_plies);
         self
     }
@@ -83,7 +83,7 @@ impl InfoCommand {
     /// Select the selective search depth (in plies).
     ///
     /// This should be set together with depth.
-    pub fn with_sel_depth_plies(mut self, sel_depth_plies: usize) -> Self {
+    pub fn with_sel_depth_plies(mut self, sel_depth_plies: u32) -> Self {
         self.sel_depth_plies = Some(sel_depth_plies);
         self
     }
@@ -95,25 +95,25 @@ impl InfoCommand {
     }
 
     /// Set the number of nodes that the engine has searched.
-    pub fn with_node_count(mut self, node_count: usize) -> Self {
+    pub fn with_node_count(mut self, node_count: u64) -> Self {
         self.node_count = Some(node_count);
         self
     }
 
     /// Set the number of nodes that the engine has searched per second.
-    pub fn with_nodes_per_second(mut self, nodes_per_second: usize) -> Self {
+    pub fn with_nodes_per_second(mut self, nodes_per_second: u64) -> Self {
         self.nodes_per_second = Some(nodes_per_second);
         self
     }
 
     /// Set the number of positions that were found in endgame table bases.
-    pub fn with_endgame_tb_hits(mut self, endgame_tb_hits: usize) -> Self {
+    pub fn with_endgame_tb_hits(mut self, endgame_tb_hits: u64) -> Self {
         self.endgame_tb_hits = Some(endgame_tb_hits);
         self
     }
 
     /// Set the number of positions that were found in the shredder endgame databases.
-    pub fn with_shredder_tb_hits(mut self, shredder_tb_hits: usize) -> Self {
+    pub fn with_shredder_tb_hits(mut self, shredder_tb_hits: u64) -> Self {
         self.shredder_tb_hits = Some(shredder_tb_hits);
         self
     }
@@ -142,7 +142,7 @@ impl InfoCommand {
     /// Set the k-th best principal variation (PV).
     ///
     /// `k = 1` represents the best line, `k = 2` the second best, etc.
-    pub fn with_multi_pv<L>(mut self, kth_best: usize, principal_variation: L) -> Self
+    pub fn with_multi_pv<L>(mut self, kth_best: u32, principal_variation: L) -> Self
     where
         L: Into<MoveLine>,
     {
@@ -159,19 +159,19 @@ impl InfoCommand {
     /// Set the number of the currently searched move.
     ///
     /// The first move should be `1`, not `0`.
-    pub fn with_curr_move_number(mut self, curr_move_number: usize) -> Self {
+    pub fn with_curr_move_number(mut self, curr_move_number: u32) -> Self {
         self.curr_move_number = Some(curr_move_number);
         self
     }
 
     /// Set the engine's CPU load, in permill.
-    pub fn with_cpu_load_permill(mut self, cpu_load_permill: usize) -> Self {
+    pub fn with_cpu_load_permill(mut self, cpu_load_permill: u16) -> Self {
         self.cpu_load_permill = Some(cpu_load_permill);
         self
     }
 
     /// Set the load of the hash table in permill.
-    pub fn with_hash_full_permill(mut self, hash_full_permill: usize) -> Self {
+    pub fn with_hash_full_permill(mut self, hash_full_permill: u16) -> Self {
         self.hash_full_permill = Some(hash_full_permill);
         self
     }
@@ -201,7 +201,7 @@ impl InfoCommand {
     /// Set the currently calculated line for a given CPU.
     ///
     /// The engine should only send this if the option `UCI_ShowCurrLine` is set to `true`.
-    pub fn with_curr_line_for_cpu<L>(mut self, cpu_num: usize, line: L) -> Self
+    pub fn with_curr_line_for_cpu<L>(mut self, cpu_num: u32, line: L) -> Self
     where
         L: Into<MoveLine>,
     {
@@ -217,6 +217,124 @@ impl InfoCommand {
         self.string = Some(string.into());
         self
     }
+
+    /// The search depth (in plies).
+    pub fn depth_plies(&self) -> Option<u32> {
+        self.depth_plies
+    }
+
+    /// The number of nodes searched.
+    pub fn node_count(&self) -> Option<u64> {
+        self.node_count
+    }
+
+    /// The k-th best principal variation (PV), with `k = 1` for the best line.
+    pub fn pv(&self) -> Option<(u32, &MoveLine)> {
+        self.pv.as_ref().map(|(kth_best, pv)| (*kth_best, pv))
+    }
+
+    /// The currently searched move.
+    pub fn curr_move(&self) -> Option<Move> {
+        self.curr_move
+    }
+
+    /// The number of the currently searched move.
+    pub fn curr_move_number(&self) -> Option<u32> {
+        self.curr_move_number
+    }
+
+    /// The currently calculated line, with the CPU number.
+    pub fn curr_line(&self) -> Option<(u32, &MoveLine)> {
+        self.curr_line
+            .as_ref()
+            .map(|(cpu_num, line)| (*cpu_num, line))
+    }
+
+    /// The text sent as info.
+    pub fn string(&self) -> Option<&str> {
+        self.string.as_deref()
+    }
+
+    /// Check that the info command conforms to the UCI protocol.
+    ///
+    /// Returns all violations, if there are any.
+    /// Formatting the command omits or clamps the invalid fields, so the output always conforms.
+    pub fn validate(&self) -> Result<(), Vec<InfoViolation>> {
+        let mut violations = Vec::new();
+
+        if self.sel_depth_plies.is_some() && self.depth_plies.is_none() {
+            violations.push(InfoViolation::SelDepthWithoutDepth);
+        }
+
+        if let Some(hash_full) = self.hash_full_permill.filter(|&permill| permill > 1000) {
+            violations.push(InfoViolation::HashFullAbovePermill(hash_full));
+        }
+
+        if let Some(cpu_load) = self.cpu_load_permill.filter(|&permill| permill > 1000) {
+            violations.push(InfoViolation::CpuLoadAbovePermill(cpu_load));
+        }
+
+        if self.curr_move_number == Some(0) {
+            violations.push(InfoViolation::CurrMoveNumberZero);
+        }
+
+        if matches!(self.pv, Some((0, _))) {
+            violations.push(InfoViolation::MultiPvZero);
+        }
+
+        if self
+            .string
+            .as_ref()
+            .is_some_and(|string| string.contains(['\r', '\n']))
+        {
+            violations.push(InfoViolation::StringWithLineBreak);
+        }
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+}
+
+/// A rule of the UCI protocol that an [`InfoCommand`] doesn't follow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InfoViolation {
+    /// `seldepth` is sent without `depth`.
+    SelDepthWithoutDepth,
+
+    /// `hashfull` is more than 1000 permill.
+    HashFullAbovePermill(u16),
+
+    /// `cpuload` is more than 1000 permill.
+    CpuLoadAbovePermill(u16),
+
+    /// `currmovenumber` is `0`, but the first move is `1`.
+    CurrMoveNumberZero,
+
+    /// `multipv` is `0`, but the best line is `1`.
+    MultiPvZero,
+
+    /// The `string` contains a line break, which would end the command.
+    StringWithLineBreak,
+}
+
+impl Display for InfoViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InfoViolation::SelDepthWithoutDepth => write!(f, "seldepth requires depth"),
+            InfoViolation::HashFullAbovePermill(permill) => {
+                write!(f, "hashfull {permill} is above 1000 permill")
+            }
+            InfoViolation::CpuLoadAbovePermill(permill) => {
+                write!(f, "cpuload {permill} is above 1000 permill")
+            }
+            InfoViolation::CurrMoveNumberZero => write!(f, "currmovenumber starts at 1"),
+            InfoViolation::MultiPvZero => write!(f, "multipv starts at 1"),
+            InfoViolation::StringWithLineBreak => write!(f, "string contains a line break"),
+        }
+    }
 }
 
 impl Display for InfoCommand {
@@ -231,7 +349,8 @@ impl Display for InfoCommand {
         }
 
         // seldepth 1
-        if let Some(sel_depth) = self.sel_depth_plies {
+        // Only valid after depth
+        if let Some(sel_depth) = self.sel_depth_plies.filter(|_| self.depth_plies.is_some()) {
             output += &format!(" seldepth {sel_depth}");
         }
 
@@ -271,18 +390,19 @@ impl Display for InfoCommand {
         }
 
         // currmovenumber 14
-        if let Some(curr_move_number) = &self.curr_move_number {
+        // The first move is 1
+        if let Some(curr_move_number) = self.curr_move_number.filter(|&number| number > 0) {
             output += &format!(" currmovenumber {curr_move_number}");
         }
 
         // cpuload 10
         if let Some(cpuload) = self.cpu_load_permill {
-            output += &format!(" cpuload {cpuload}");
+            output += &format!(" cpuload {}", cpuload.min(1000));
         }
 
-        // hashfull
+        // hashfull 500
         if let Some(hashfull) = self.hash_full_permill {
-            output += &format!(" hashfull {hashfull}");
+            output += &format!(" hashfull {}", hashfull.min(1000));
         }
 
         // refutation d1h5 g6h5
@@ -302,8 +422,18 @@ impl Display for InfoCommand {
         }
 
         // multipv 1 pv e2e4
-        if let Some((multi_pv, pv)) = &self.pv {
-            output += &format!(" multipv {multi_pv} pv {pv}");
+        // The best line is 1, an invalid multipv is left out
+        match &self.pv {
+            Some((0, pv)) => output += &format!(" pv {pv}"),
+            Some((multi_pv, pv)) => output += &format!(" multipv {multi_pv} pv {pv}"),
+            None => {}
+        }
+
+        // string Hello world
+        // The string consumes the rest of the line, so it must be last
+        // Line breaks would end the command early
+        if let Some(string) = &self.string {
+            output += &format!(" string {}", string.replace(['\r', '\n'], " "));
         }
 
         write!(f, "{output}")
@@ -313,7 +443,7 @@ impl Display for InfoCommand {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::chess::{Evaluation, File, Move, MoveLine, Rank, Score, Square};
+    use crate::chess::{Evaluation, MoveLine, Score, Wdl};
 
     #[test]
     fn format_info_search() {
@@ -331,4 +461,98 @@ mod tests {
 
         assert_eq!(actual, "info depth 3 seldepth 3 score cp 37 nodes 191 nps 191000 tbhits 0 time 1 multipv 1 pv c2c4 a7a6 e2e4".to_string());
     }
+
+    #[test]
+    fn format_info_wdl() {
+        let cmd = InfoCommand::new()
+            .with_depth_plies(20)
+            .with_score(Score::new(Evaluation::Centipawns(35)).with_wdl(Wdl::new(412, 531, 57)));
+
+        let actual = format!("{cmd}");
+
+        assert_eq!(actual, "info depth 20 score cp 35 wdl 412 531 57");
+    }
+
+    #[test]
+    fn format_info_large_node_count() {
+        let cmd = InfoCommand::new()
+            .with_node_count(1_234_567_890_123)
+            .with_nodes_per_second(98_765_432_100);
+
+        let actual = format!("{cmd}");
+
+        assert_eq!(actual, "info nodes 1234567890123 nps 98765432100");
+    }
+
+    #[test]
+    fn format_info_string() {
+        let cmd = InfoCommand::new()
+            .with_string("invalid value 0 for option Hash")
+            .with_depth_plies(2);
+
+        let actual = format!("{cmd}");
+
+        assert_eq!(
+            actual,
+            "info depth 2 string invalid value 0 for option Hash"
+        );
+    }
+
+    #[test]
+    fn format_info_string_without_line_breaks() {
+        let cmd = InfoCommand::new().with_string("first\nsecond\r\nthird");
+
+        assert_eq!(cmd.to_string(), "info string first second  third");
+    }
+
+    #[test]
+    fn format_info_without_violations() {
+        let cmd = InfoCommand::new()
+            .with_sel_depth_plies(5)
+            .with_hash_full_permill(1001)
+            .with_cpu_load_permill(2000)
+            .with_curr_move_number(0)
+            .with_multi_pv(0, "e2e4".parse::<MoveLine>().unwrap());
+
+        let actual = format!("{cmd}");
+
+        assert_eq!(actual, "info cpuload 1000 hashfull 1000 pv e2e4");
+    }
+
+    #[test]
+    fn validate_info_ok() {
+        let cmd = InfoCommand::new()
+            .with_depth_plies(3)
+            .with_sel_depth_plies(5)
+            .with_hash_full_permill(1000)
+            .with_cpu_load_permill(0)
+            .with_curr_move_number(1)
+            .with_multi_pv(2, MoveLine::new())
+            .with_string("fine");
+
+        assert_eq!(cmd.validate(), Ok(()));
+    }
+
+    #[test]
+    fn validate_info_returns_all_violations() {
+        let cmd = InfoCommand::new()
+            .with_sel_depth_plies(5)
+            .with_hash_full_permill(1001)
+            .with_cpu_load_permill(2000)
+            .with_curr_move_number(0)
+            .with_multi_pv(0, MoveLine::new())
+            .with_string("two\nlines");
+
+        assert_eq!(
+            cmd.validate(),
+            Err(vec![
+                InfoViolation::SelDepthWithoutDepth,
+                InfoViolation::HashFullAbovePermill(1001),
+                InfoViolation::CpuLoadAbovePermill(2000),
+                InfoViolation::CurrMoveNumberZero,
+                InfoViolation::MultiPvZero,
+                InfoViolation::StringWithLineBreak,
+            ])
+        );
+    }
 }
@@ -1,5 +1,7 @@
 //! Commands sent from the engine to the GUI.
 
+use std::fmt::Display;
+
 mod best_move;
 mod copy_protection;
 mod id;
@@ -10,10 +12,16 @@ mod registration;
 pub use self::best_move::BestMoveCommand;
 pub use self::copy_protection::CopyProtectionCommand;
 pub use self::id::IdCommand;
-pub use self::info::InfoCommand;
+pub use self::info::{InfoCommand, InfoViolation};
+pub use self::option::{
+    CheckOption, ComboOption, EngineOption, OptionType, SpinOption, StringOption,
+};
 pub use self::registration::RegistrationCommand;
 
+pub(crate) use self::option::EMPTY_STRING;
+
 /// A command sent from the engine to the GUI.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum EngineToGuiCommand {
     /// Identify the engine to the GUI.
     Id(IdCommand),
@@ -48,5 +56,58 @@ pub enum EngineToGuiCommand {
     /// This command tells the GUI which parameters can be changed in the engine.
     ///
     /// This should be sent once at engine startup after the `uci` and the `id` commands if any parameter can be changed in the engine.
-    Option(),
+    Option(EngineOption),
+}
+
+impl Display for EngineToGuiCommand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EngineToGuiCommand::Id(id) => write!(f, "{id}"),
+            EngineToGuiCommand::UciOk => write!(f, "uciok"),
+            EngineToGuiCommand::ReadyOk => write!(f, "readyok"),
+            EngineToGuiCommand::BestMove(best_move) => write!(f, "{best_move}"),
+            EngineToGuiCommand::CopyProtection(copy_protection) => write!(f, "{copy_protection}"),
+            EngineToGuiCommand::Registration(registration) => write!(f, "{registration}"),
+            EngineToGuiCommand::Info(info) => write!(f, "{info}"),
+            EngineToGuiCommand::Option(option) => write!(f, "{option}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chess::Move;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(
+        EngineToGuiCommand::Id(IdCommand::new_name("Engine")),
+        "id name Engine"
+    )]
+    #[case(EngineToGuiCommand::UciOk, "uciok")]
+    #[case(EngineToGuiCommand::ReadyOk, "readyok")]
+    #[case(
+        EngineToGuiCommand::BestMove(BestMoveCommand::new("e2e4".parse::<Move>().unwrap())),
+        "bestmove e2e4"
+    )]
+    #[case(
+        EngineToGuiCommand::CopyProtection(CopyProtectionCommand::Ok),
+        "copyprotection ok"
+    )]
+    #[case(
+        EngineToGuiCommand::Registration(RegistrationCommand::Error),
+        "registration error"
+    )]
+    #[case(
+        EngineToGuiCommand::Info(Box::new(InfoCommand::new().with_depth_plies(2))),
+        "info depth 2"
+    )]
+    #[case(
+        EngineToGuiCommand::Option(EngineOption::hash(1, 1024, 16)),
+        "option name Hash type spin default 16 min 1 max 1024"
+    )]
+    fn format_engine_to_gui_cmd(#[case] input: EngineToGuiCommand, #[case] expected: String) {
+        assert_eq!(input.to_string(), expected);
+    }
 }
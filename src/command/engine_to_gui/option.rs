@@ -1,4 +1,15 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::{chess::Variant, command::CommandParseError};
+
+/// The value that represents an empty string, as the default of a string option and in `setoption`.
+pub(crate) const EMPTY_STRING: &str = "<empty>";
+
+/// The keywords that introduce the parameters of an option.
+const PARAMETER_KEYWORDS: [&str; 4] = ["default", "min", "max", "var"];
+
 /// An engine option that can be set by the GUI.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct EngineOption {
     /// The name of the option.
     pub name: String,
@@ -8,6 +19,60 @@ pub struct EngineOption {
 }
 
 impl EngineOption {
+    /// The name of the option for the size of the hashtable in MB.
+    pub const HASH: &'static str = "Hash";
+
+    /// The name of the option for the number of search threads.
+    pub const THREADS: &'static str = "Threads";
+
+    /// The name of the option for the number of principal variations to show.
+    pub const MULTI_PV: &'static str = "MultiPV";
+
+    /// The name of the option telling the engine that it's allowed to ponder.
+    pub const PONDER: &'static str = "Ponder";
+
+    /// The name of the option to use the engine's own opening book.
+    pub const OWN_BOOK: &'static str = "OwnBook";
+
+    /// The name of the button to clear the hashtable.
+    pub const CLEAR_HASH: &'static str = "Clear Hash";
+
+    /// The name of the option to show the line that is currently searched.
+    pub const SHOW_CURR_LINE: &'static str = "UCI_ShowCurrLine";
+
+    /// The name of the option to show refutations.
+    pub const SHOW_REFUTATIONS: &'static str = "UCI_ShowRefutations";
+
+    /// The name of the option to limit the strength of the engine to `UCI_Elo`.
+    pub const LIMIT_STRENGTH: &'static str = "UCI_LimitStrength";
+
+    /// The name of the option for the strength of the engine in Elo.
+    pub const ELO: &'static str = "UCI_Elo";
+
+    /// The name of the option telling the engine that it's analysing instead of playing a game.
+    pub const ANALYSE_MODE: &'static str = "UCI_AnalyseMode";
+
+    /// The name of the option to play Chess960.
+    pub const CHESS960: &'static str = "UCI_Chess960";
+
+    /// The name of the option with information about the engine.
+    pub const ENGINE_ABOUT: &'static str = "UCI_EngineAbout";
+
+    /// The name of the option to show win, draw and loss chances.
+    pub const SHOW_WDL: &'static str = "UCI_ShowWDL";
+
+    /// The name of the option for the paths to the Syzygy tablebases.
+    pub const SYZYGY_PATH: &'static str = "SyzygyPath";
+
+    /// The name of the option telling the engine about its opponent.
+    pub const OPPONENT: &'static str = "UCI_Opponent";
+
+    /// The name of the option to select the chess variant to play.
+    pub const VARIANT: &'static str = "UCI_Variant";
+
+    /// The name of the option for the file that all communication with the GUI is logged to.
+    pub const DEBUG_LOG_FILE: &'static str = "Debug Log File";
+
     /// Create a new engine option.
     pub fn new<N>(name: N, r#type: OptionType) -> Self
     where
@@ -30,15 +95,213 @@ impl EngineOption {
     /// - `min_mb` > `max_mb`
     /// - `default_mb` < `min_mb`
     /// - `default_mb` > `max_mb`
-    pub fn hash(min_mb: usize, max_mb: usize, default_mb: usize) -> Self {
+    pub fn hash(min_mb: u32, max_mb: u32, default_mb: u32) -> Self {
+        Self::new(
+            Self::HASH,
+            OptionType::Spin(SpinOption::new(
+                min_mb.into(),
+                max_mb.into(),
+                default_mb.into(),
+            )),
+        )
+    }
+
+    /// Create a new option to select the chess variant to play, called `UCI_Variant`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `default` is not one of the given `variants`.
+    pub fn variant(variants: &[Variant], default: Variant) -> Self {
         Self::new(
-            "Hash",
-            OptionType::Spin(SpinOption::new(min_mb, max_mb, default_mb)),
+            Self::VARIANT,
+            OptionType::Combo(ComboOption::new(
+                variants.iter().map(|variant| variant.to_string()),
+                default.to_string(),
+            )),
         )
     }
+
+    /// Create a new checkbox with the given name and default value.
+    fn check<N>(name: N, default: bool) -> Self
+    where
+        N: Into<String>,
+    {
+        Self::new(name, OptionType::Check(CheckOption { default }))
+    }
+
+    /// Create a new text field with the given name and default value.
+    fn string<N, D>(name: N, default: D) -> Self
+    where
+        N: Into<String>,
+        D: Into<String>,
+    {
+        Self::new(
+            name,
+            OptionType::String(StringOption {
+                default: default.into(),
+            }),
+        )
+    }
+
+    /// Create a new option for the number of search threads, called `Threads`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `default` is not between 1 and `max`.
+    pub fn threads(max: u32, default: u32) -> Self {
+        Self::new(
+            Self::THREADS,
+            OptionType::Spin(SpinOption::new(1, max.into(), default.into())),
+        )
+    }
+
+    /// Create a new option for the number of principal variations to show, called `MultiPV`.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `default` is not between 1 and `max`.
+    pub fn multi_pv(max: u32, default: u32) -> Self {
+        Self::new(
+            Self::MULTI_PV,
+            OptionType::Spin(SpinOption::new(1, max.into(), default.into())),
+        )
+    }
+
+    /// Create a new option telling the engine that it's allowed to ponder, called `Ponder`.
+    ///
+    /// The engine can use this to adjust its time management.
+    pub fn ponder() -> Self {
+        Self::check(Self::PONDER, false)
+    }
+
+    /// Create a new option to use the engine's own opening book, called `OwnBook`.
+    pub fn own_book(default: bool) -> Self {
+        Self::check(Self::OWN_BOOK, default)
+    }
+
+    /// Create a new button to clear the hashtable, called `Clear Hash`.
+    pub fn clear_hash() -> Self {
+        Self::new(Self::CLEAR_HASH, OptionType::Button)
+    }
+
+    /// Create a new option to show the line that is currently searched, called `UCI_ShowCurrLine`.
+    pub fn show_curr_line() -> Self {
+        Self::check(Self::SHOW_CURR_LINE, false)
+    }
+
+    /// Create a new option to show refutations, called `UCI_ShowRefutations`.
+    pub fn show_refutations() -> Self {
+        Self::check(Self::SHOW_REFUTATIONS, false)
+    }
+
+    /// Create a new option to limit the strength of the engine, called `UCI_LimitStrength`.
+    ///
+    /// The strength is given by the [`EngineOption::elo`] option.
+    pub fn limit_strength() -> Self {
+        Self::check(Self::LIMIT_STRENGTH, false)
+    }
+
+    /// Create a new option for the strength of the engine in Elo, called `UCI_Elo`.
+    ///
+    /// The strength is only limited if [`EngineOption::limit_strength`] is set.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `default` is not between `min` and `max`.
+    pub fn elo(min: u32, max: u32, default: u32) -> Self {
+        Self::new(
+            Self::ELO,
+            OptionType::Spin(SpinOption::new(min.into(), max.into(), default.into())),
+        )
+    }
+
+    /// Create a new option telling the engine that it's analysing, called `UCI_AnalyseMode`.
+    pub fn analyse_mode() -> Self {
+        Self::check(Self::ANALYSE_MODE, false)
+    }
+
+    /// Create a new option to play Chess960, called `UCI_Chess960`.
+    ///
+    /// Castling moves are then sent as the king capturing its own rook.
+    pub fn chess960() -> Self {
+        Self::check(Self::CHESS960, false)
+    }
+
+    /// Create a new option with information about the engine, called `UCI_EngineAbout`.
+    ///
+    /// The text is shown by the GUI, it can't be changed.
+    pub fn engine_about<S>(about: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self::string(Self::ENGINE_ABOUT, about)
+    }
+
+    /// Create a new option to show win, draw and loss chances, called `UCI_ShowWDL`.
+    pub fn show_wdl() -> Self {
+        Self::check(Self::SHOW_WDL, false)
+    }
+
+    /// Create a new option for the paths to the Syzygy tablebases, called `SyzygyPath`.
+    ///
+    /// Multiple paths are separated by `;` on Windows and by `:` on other systems.
+    pub fn syzygy_path() -> Self {
+        Self::string(Self::SYZYGY_PATH, "")
+    }
+
+    /// Create a new option telling the engine about its opponent, called `UCI_Opponent`.
+    ///
+    /// The value can be parsed with [`SetOptionCommand::opponent`].
+    ///
+    /// [`SetOptionCommand::opponent`]: crate::command::gui_to_engine::SetOptionCommand::opponent
+    pub fn opponent() -> Self {
+        Self::string(Self::OPPONENT, "")
+    }
+
+    /// Create a new option for the file that all communication with the GUI is logged to,
+    /// called `Debug Log File`.
+    ///
+    /// This is not part of the UCI protocol, but many engines support it.
+    /// The engine loops declare and handle this option on their own.
+    pub fn debug_log_file() -> Self {
+        Self::string(Self::DEBUG_LOG_FILE, "")
+    }
+}
+
+impl Display for EngineOption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "option name {} type {}", self.name, self.r#type)
+    }
+}
+
+impl FromStr for EngineOption {
+    type Err = CommandParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens: Vec<_> = s.split_ascii_whitespace().collect();
+
+        let ["option", "name", rest @ ..] = tokens.as_slice() else {
+            return Err(CommandParseError);
+        };
+
+        let type_index = rest
+            .iter()
+            .position(|&token| token == "type")
+            .ok_or(CommandParseError)?;
+
+        if type_index == 0 {
+            return Err(CommandParseError);
+        }
+
+        let name = rest[..type_index].join(" ");
+        let r#type = rest[type_index + 1..].join(" ").parse::<OptionType>()?;
+
+        Ok(EngineOption::new(name, r#type))
+    }
 }
 
 /// The type of the engine option.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum OptionType {
     /// A checkbox that can take the values `true` or `false`.
     Check(CheckOption),
@@ -56,22 +319,156 @@ pub enum OptionType {
     String(StringOption),
 }
 
+impl Display for OptionType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OptionType::Check(check) => write!(f, "check default {}", check.default),
+            OptionType::Spin(spin) => write!(
+                f,
+                "spin default {} min {} max {}",
+                spin.default, spin.min, spin.max
+            ),
+            OptionType::Combo(combo) => {
+                write!(f, "combo default {}", combo.default)?;
+
+                for value in &combo.values {
+                    write!(f, " var {value}")?;
+                }
+
+                Ok(())
+            }
+            OptionType::Button => write!(f, "button"),
+            OptionType::String(string) => {
+                let default = if string.default.is_empty() {
+                    EMPTY_STRING
+                } else {
+                    &string.default
+                };
+
+                write!(f, "string default {default}")
+            }
+        }
+    }
+}
+
+impl FromStr for OptionType {
+    type Err = CommandParseError;
+
+    /// Parse the option type, e.g. `spin default 1 min -5 max 5`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens: Vec<_> = s.split_ascii_whitespace().collect();
+
+        let [type_name, rest @ ..] = tokens.as_slice() else {
+            return Err(CommandParseError);
+        };
+
+        let parameters = parse_parameters(rest)?;
+        let get = |keyword: &str| {
+            parameters
+                .iter()
+                .find(|(parameter, _)| *parameter == keyword)
+                .map(|(_, value)| value.as_str())
+        };
+        let get_spin_value = |keyword: &str| {
+            get(keyword)
+                .ok_or(CommandParseError)?
+                .parse::<i64>()
+                .map_err(|_| CommandParseError)
+        };
+
+        let r#type = match *type_name {
+            "check" => {
+                let default = match get("default") {
+                    Some("true") => true,
+                    Some("false") => false,
+                    _ => return Err(CommandParseError),
+                };
+
+                OptionType::Check(CheckOption { default })
+            }
+            "spin" => {
+                let default = get_spin_value("default")?;
+                let min = get_spin_value("min")?;
+                let max = get_spin_value("max")?;
+
+                if min > max || default < min || default > max {
+                    return Err(CommandParseError);
+                }
+
+                OptionType::Spin(SpinOption::new(min, max, default))
+            }
+            "combo" => {
+                let default = get("default").ok_or(CommandParseError)?;
+                let values: Vec<_> = parameters
+                    .iter()
+                    .filter(|(parameter, _)| *parameter == "var")
+                    .map(|(_, value)| value.as_str())
+                    .collect();
+
+                if !values.contains(&default) {
+                    return Err(CommandParseError);
+                }
+
+                OptionType::Combo(ComboOption::new(values, default))
+            }
+            "button" if parameters.is_empty() => OptionType::Button,
+            "string" => {
+                let default = match get("default") {
+                    Some(EMPTY_STRING) | None => "",
+                    Some(default) => default,
+                };
+
+                OptionType::String(StringOption {
+                    default: default.to_string(),
+                })
+            }
+            _ => return Err(CommandParseError),
+        };
+
+        Ok(r#type)
+    }
+}
+
+/// Split the tokens into the parameters of an option, e.g. `default 1 min 0 max 2`.
+///
+/// The value of a parameter consists of all tokens up to the next keyword.
+fn parse_parameters<'a>(tokens: &[&'a str]) -> Result<Vec<(&'a str, String)>, CommandParseError> {
+    let mut parameters: Vec<(&str, Vec<&str>)> = Vec::new();
+
+    for &token in tokens {
+        if PARAMETER_KEYWORDS.contains(&token) {
+            parameters.push((token, Vec::new()));
+        } else if let Some((_, value)) = parameters.last_mut() {
+            value.push(token);
+        } else {
+            return Err(CommandParseError);
+        }
+    }
+
+    Ok(parameters
+        .into_iter()
+        .map(|(keyword, value)| (keyword, value.join(" ")))
+        .collect())
+}
+
 /// A checkbox that can take the values `true` or `false`.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CheckOption {
     /// The default value for the checkbox.
     pub default: bool,
 }
 
 /// A spin wheel that can be an integer in a certain range.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SpinOption {
     /// The default value for the spin wheel.
-    pub default: usize,
+    pub default: i64,
 
     /// The minimum value of the spin wheel.
-    pub min: usize,
+    pub min: i64,
 
     /// The maximum value of the spin wheel.
-    pub max: usize,
+    pub max: i64,
 }
 
 impl SpinOption {
@@ -79,7 +476,7 @@ impl SpinOption {
     ///
     /// The `min` must be smaller or equal to `max`.
     /// The `default` must be between `min` and `max`.
-    pub fn new(min: usize, max: usize, default: usize) -> Self {
+    pub fn new(min: i64, max: i64, default: i64) -> Self {
         assert!(min <= max, "The min {min} bigger than the max {max}");
         assert!(
             min <= default && default <= max,
@@ -91,6 +488,7 @@ impl SpinOption {
 }
 
 /// A combo box that can have different predefined strings as value.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ComboOption {
     /// The default value for the combo box.
     pub default: String,
@@ -99,8 +497,178 @@ pub struct ComboOption {
     pub values: Vec<String>,
 }
 
+impl ComboOption {
+    /// Create a new combo option.
+    ///
+    /// The `default` must be one of the `values`.
+    pub fn new<I, S, D>(values: I, default: D) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+        D: Into<String>,
+    {
+        let values: Vec<String> = values.into_iter().map(Into::into).collect();
+        let default = default.into();
+
+        assert!(
+            values.contains(&default),
+            "The default {default} must be one of the values {values:?}"
+        );
+
+        Self { default, values }
+    }
+}
+
 /// A text field that has a string as a value.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct StringOption {
     /// The default value of the text field.
     pub default: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(
+        EngineOption::new("Nullmove", OptionType::Check(CheckOption { default: true })),
+        "option name Nullmove type check default true"
+    )]
+    #[case(
+        EngineOption::new("Selectivity", OptionType::Spin(SpinOption::new(0, 4, 2))),
+        "option name Selectivity type spin default 2 min 0 max 4"
+    )]
+    #[case(
+        EngineOption::new("Contempt", OptionType::Spin(SpinOption::new(-100, 100, -10))),
+        "option name Contempt type spin default -10 min -100 max 100"
+    )]
+    #[case(
+        EngineOption::new(
+            "Style",
+            OptionType::Combo(ComboOption::new(["Solid", "Normal", "Risky"], "Normal"))
+        ),
+        "option name Style type combo default Normal var Solid var Normal var Risky"
+    )]
+    #[case(
+        EngineOption::new(
+            "NalimovPath",
+            OptionType::String(StringOption { default: "c:\\".to_string() })
+        ),
+        "option name NalimovPath type string default c:\\"
+    )]
+    #[case(
+        EngineOption::new(
+            "SyzygyPath",
+            OptionType::String(StringOption { default: String::new() })
+        ),
+        "option name SyzygyPath type string default <empty>"
+    )]
+    #[case(
+        EngineOption::new("Clear Hash", OptionType::Button),
+        "option name Clear Hash type button"
+    )]
+    #[case(
+        EngineOption::hash(1, 33554432, 16),
+        "option name Hash type spin default 16 min 1 max 33554432"
+    )]
+    #[case(
+        EngineOption::threads(512, 1),
+        "option name Threads type spin default 1 min 1 max 512"
+    )]
+    #[case(
+        EngineOption::multi_pv(500, 1),
+        "option name MultiPV type spin default 1 min 1 max 500"
+    )]
+    #[case(EngineOption::ponder(), "option name Ponder type check default false")]
+    #[case(
+        EngineOption::own_book(true),
+        "option name OwnBook type check default true"
+    )]
+    #[case(
+        EngineOption::show_curr_line(),
+        "option name UCI_ShowCurrLine type check default false"
+    )]
+    #[case(
+        EngineOption::show_refutations(),
+        "option name UCI_ShowRefutations type check default false"
+    )]
+    #[case(
+        EngineOption::limit_strength(),
+        "option name UCI_LimitStrength type check default false"
+    )]
+    #[case(
+        EngineOption::elo(1320, 3190, 1320),
+        "option name UCI_Elo type spin default 1320 min 1320 max 3190"
+    )]
+    #[case(
+        EngineOption::analyse_mode(),
+        "option name UCI_AnalyseMode type check default false"
+    )]
+    #[case(
+        EngineOption::chess960(),
+        "option name UCI_Chess960 type check default false"
+    )]
+    #[case(
+        EngineOption::engine_about("My Engine by Me, see example.com"),
+        "option name UCI_EngineAbout type string default My Engine by Me, see example.com"
+    )]
+    #[case(
+        EngineOption::show_wdl(),
+        "option name UCI_ShowWDL type check default false"
+    )]
+    #[case(
+        EngineOption::syzygy_path(),
+        "option name SyzygyPath type string default <empty>"
+    )]
+    #[case(
+        EngineOption::opponent(),
+        "option name UCI_Opponent type string default <empty>"
+    )]
+    #[case(
+        EngineOption::debug_log_file(),
+        "option name Debug Log File type string default <empty>"
+    )]
+    #[case(EngineOption::clear_hash(), "option name Clear Hash type button")]
+    fn format_and_parse_option(#[case] option: EngineOption, #[case] line: &str) {
+        assert_eq!(option.to_string(), line);
+        assert_eq!(line.parse::<EngineOption>(), Ok(option));
+    }
+
+    #[rstest]
+    #[case(
+        "option name Skill Level type spin default -20 min -20 max -10",
+        EngineOption::new("Skill Level", OptionType::Spin(SpinOption::new(-20, -10, -20)))
+    )]
+    #[case(
+        "option name Big type spin default 0 min -9223372036854775808 max 9223372036854775807",
+        EngineOption::new("Big", OptionType::Spin(SpinOption::new(i64::MIN, i64::MAX, 0)))
+    )]
+    #[case(
+        "option  name Book File  type string default my book.bin",
+        EngineOption::new(
+            "Book File",
+            OptionType::String(StringOption { default: "my book.bin".to_string() })
+        )
+    )]
+    fn parse_option_ok(#[case] input: &str, #[case] expected: EngineOption) {
+        assert_eq!(input.parse(), Ok(expected));
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("option name type check default true")]
+    #[case("option name Hash")]
+    #[case("option name Hash type")]
+    #[case("option name Hash type dial default 1")]
+    #[case("option name Hash type spin default 1 min 2 max 3")]
+    #[case("option name Hash type spin default 1 min 0")]
+    #[case("option name Hash type spin default -1.5 min -2 max 0")]
+    #[case("option name Ponder type check default yes")]
+    #[case("option name Style type combo default Wild var Solid var Normal")]
+    #[case("option name Clear Hash type button default true")]
+    fn parse_option_err(#[case] input: &str) {
+        assert!(input.parse::<EngineOption>().is_err());
+    }
+}
@@ -1,6 +1,7 @@
 use std::fmt::Display;
 
 /// A command for engines that need registration to communicate the status to the GUI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum RegistrationCommand {
     /// Tell the GUI that the registration is being checked now.
     Checking,
@@ -0,0 +1,282 @@
+use std::{fmt::Display, str::FromStr, time::Duration};
+
+use crate::{
+    chess::{Move, MoveLine},
+    command::CommandParseError,
+};
+
+/// The keywords that can follow the `go` command.
+const GO_KEYWORDS: [&str; 12] = [
+    "searchmoves",
+    "ponder",
+    "wtime",
+    "btime",
+    "winc",
+    "binc",
+    "movestogo",
+    "depth",
+    "nodes",
+    "mate",
+    "movetime",
+    "infinite",
+];
+
+/// Start calculating on the current position set up with the `position` command.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct GoCommand {
+    /// Restrict the search to these moves only.
+    pub search_moves: Option<MoveLine>,
+
+    /// Start searching in pondering mode.
+    ///
+    /// The last move sent in the `position` command is the ponder move.
+    /// The engine must not exit the search in ponder mode, even if it's mate,
+    /// until `ponderhit` or `stop` is received.
+    pub ponder: bool,
+
+    /// The time that White has left on the clock.
+    pub white_time: Option<Duration>,
+
+    /// The time that Black has left on the clock.
+    pub black_time: Option<Duration>,
+
+    /// White's increment per move.
+    pub white_increment: Option<Duration>,
+
+    /// Black's increment per move.
+    pub black_increment: Option<Duration>,
+
+    /// The number of moves to the next time control.
+    ///
+    /// If this is not set, the game is in sudden death.
+    pub moves_to_go: Option<u32>,
+
+    /// Search this many plies only.
+    pub depth: Option<u32>,
+
+    /// Search this many nodes only.
+    pub nodes: Option<u64>,
+
+    /// Search for a mate in this many moves.
+    pub mate: Option<u32>,
+
+    /// Search exactly this long.
+    pub move_time: Option<Duration>,
+
+    /// Search until the `stop` command is received.
+    pub infinite: bool,
+}
+
+impl GoCommand {
+    /// Create a new go command without any limits.
+    pub fn new() -> Self {
+        Self {
+            ..Default::default()
+        }
+    }
+}
+
+/// Parse a time in milliseconds.
+///
+/// Some GUIs send negative times if a player is out of time, these are treated as zero.
+fn parse_millis(value: &str) -> Result<Duration, CommandParseError> {
+    let millis = value.parse::<i64>().map_err(|_| CommandParseError)?;
+    Ok(Duration::from_millis(millis.max(0) as u64))
+}
+
+impl FromStr for GoCommand {
+    type Err = CommandParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut tokens = s.split_ascii_whitespace().peekable();
+
+        if tokens.next() != Some("go") {
+            return Err(CommandParseError);
+        }
+
+        let mut go = GoCommand::new();
+
+        while let Some(keyword) = tokens.next() {
+            if keyword == "searchmoves" {
+                let mut moves = MoveLine::new();
+
+                while let Some(mv) = tokens
+                    .peek()
+                    .filter(|token| !GO_KEYWORDS.contains(token))
+                    .and_then(|token| token.parse::<Move>().ok())
+                {
+                    moves.push(mv);
+                    tokens.next();
+                }
+
+                go.search_moves = Some(moves);
+                continue;
+            }
+
+            match keyword {
+                "ponder" => go.ponder = true,
+                "infinite" => go.infinite = true,
+                "wtime" | "btime" | "winc" | "binc" | "movetime" => {
+                    let time = parse_millis(tokens.next().ok_or(CommandParseError)?)?;
+
+                    match keyword {
+                        "wtime" => go.white_time = Some(time),
+                        "btime" => go.black_time = Some(time),
+                        "winc" => go.white_increment = Some(time),
+                        "binc" => go.black_increment = Some(time),
+                        _ => go.move_time = Some(time),
+                    }
+                }
+                "movestogo" | "depth" | "mate" => {
+                    let value = tokens
+                        .next()
+                        .ok_or(CommandParseError)?
+                        .parse::<u32>()
+                        .map_err(|_| CommandParseError)?;
+
+                    match keyword {
+                        "movestogo" => go.moves_to_go = Some(value),
+                        "depth" => go.depth = Some(value),
+                        _ => go.mate = Some(value),
+                    }
+                }
+                "nodes" => {
+                    let nodes = tokens
+                        .next()
+                        .ok_or(CommandParseError)?
+                        .parse::<u64>()
+                        .map_err(|_| CommandParseError)?;
+
+                    go.nodes = Some(nodes);
+                }
+                // Unknown tokens are ignored
+                _ => {}
+            }
+        }
+
+        Ok(go)
+    }
+}
+
+impl Display for GoCommand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut output = "go".to_string();
+
+        if let Some(search_moves) = &self.search_moves {
+            output += &format!(" searchmoves {search_moves}");
+        }
+
+        if self.ponder {
+            output += " ponder";
+        }
+
+        let times = [
+            ("wtime", self.white_time),
+            ("btime", self.black_time),
+            ("winc", self.white_increment),
+            ("binc", self.black_increment),
+        ];
+
+        for (keyword, time) in times {
+            if let Some(time) = time {
+                output += &format!(" {keyword} {}", time.as_millis());
+            }
+        }
+
+        if let Some(moves_to_go) = self.moves_to_go {
+            output += &format!(" movestogo {moves_to_go}");
+        }
+
+        if let Some(depth) = self.depth {
+            output += &format!(" depth {depth}");
+        }
+
+        if let Some(nodes) = self.nodes {
+            output += &format!(" nodes {nodes}");
+        }
+
+        if let Some(mate) = self.mate {
+            output += &format!(" mate {mate}");
+        }
+
+        if let Some(move_time) = self.move_time {
+            output += &format!(" movetime {}", move_time.as_millis());
+        }
+
+        if self.infinite {
+            output += " infinite";
+        }
+
+        write!(f, "{output}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("go", GoCommand::new())]
+    #[case("go infinite", GoCommand { infinite: true, ..Default::default() })]
+    #[case(
+        "go wtime 300000 btime 299000 winc 2000 binc 1000 movestogo 40",
+        GoCommand {
+            white_time: Some(Duration::from_millis(300000)),
+            black_time: Some(Duration::from_millis(299000)),
+            white_increment: Some(Duration::from_millis(2000)),
+            black_increment: Some(Duration::from_millis(1000)),
+            moves_to_go: Some(40),
+            ..Default::default()
+        }
+    )]
+    #[case(
+        "go ponder wtime -50 btime 1000",
+        GoCommand {
+            ponder: true,
+            white_time: Some(Duration::ZERO),
+            black_time: Some(Duration::from_millis(1000)),
+            ..Default::default()
+        }
+    )]
+    #[case(
+        "go searchmoves e2e4 d2d4 depth 5",
+        GoCommand {
+            search_moves: Some("e2e4 d2d4".parse().unwrap()),
+            depth: Some(5),
+            ..Default::default()
+        }
+    )]
+    #[case(
+        "go nodes 1000000000000 mate 3 movetime 500",
+        GoCommand {
+            nodes: Some(1_000_000_000_000),
+            mate: Some(3),
+            move_time: Some(Duration::from_millis(500)),
+            ..Default::default()
+        }
+    )]
+    #[case("go joho depth 3", GoCommand { depth: Some(3), ..Default::default() })]
+    fn parse_go_ok(#[case] input: &str, #[case] expected: GoCommand) {
+        assert_eq!(input.parse(), Ok(expected));
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("stop")]
+    #[case("go depth")]
+    #[case("go depth -1")]
+    #[case("go wtime soon")]
+    fn parse_go_err(#[case] input: &str) {
+        assert!(input.parse::<GoCommand>().is_err());
+    }
+
+    #[rstest]
+    #[case("go")]
+    #[case("go searchmoves e2e4 d2d4 ponder wtime 1000 btime 2000 winc 10 binc 20 movestogo 5")]
+    #[case("go depth 12 nodes 5000 mate 2 movetime 100 infinite")]
+    fn format_go(#[case] input: &str) {
+        let go = input.parse::<GoCommand>().unwrap();
+        assert_eq!(go.to_string(), input);
+    }
+}
@@ -0,0 +1,217 @@
+//! Commands sent from the GUI to the engine.
+
+use std::{fmt::Display, str::FromStr};
+
+use super::CommandParseError;
+
+mod go;
+mod opponent;
+mod position;
+mod register;
+mod set_option;
+
+pub use self::go::GoCommand;
+pub use self::opponent::{Opponent, Title};
+pub use self::position::{PositionCommand, StartPosition};
+pub use self::register::RegisterCommand;
+pub use self::set_option::SetOptionCommand;
+
+/// The names of all commands sent from the GUI to the engine.
+const COMMAND_NAMES: [&str; 11] = [
+    "uci",
+    "debug",
+    "isready",
+    "setoption",
+    "register",
+    "ucinewgame",
+    "position",
+    "go",
+    "stop",
+    "ponderhit",
+    "quit",
+];
+
+/// A command sent from the GUI to the engine.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GuiToEngineCommand {
+    /// Tell engine to use the Universal Chess Interface (UCI).
+    ///
+    /// This will be sent once as a first command after program boot to tell the engine to switch to UCI mode.
+    ///
+    /// After receiving the `uci` command the engine must identify itself with the `id` command
+    /// and send the `option` commands to tell the GUI which engine settings the engine supports if any.
+    ///
+    /// After that the engine should send `uciok` to acknowledge the UCI mode.
+    /// If no `uciok` is sent within a certain time period, the engine task will be killed by the GUI.
+    Uci,
+
+    /// Switch the debug mode of the engine on and off.
+    ///
+    /// In debug mode the engine should send additional infos to the GUI, e.g. with the `info string` command, to help debugging, e.g. the commands that the engine has received etc.
+    ///
+    /// This mode should be switched off by default and this command can be sent any time, also when the engine is thinking.
+    Debug(bool),
+
+    /// This is used to synchronize the engine with the GUI.
+    ///
+    /// When the GUI has sent a command or multiple commands that can take some time to complete, this command can be used to wait for the engine to be ready again or to ping the engine to find out if it is still alive.
+    /// E.g. this should be sent after setting the path to the tablebases as this can take some time.
+    ///
+    /// This command is also required once before the engine is asked to do any search to wait for the engine to finish initializing.
+    ///
+    /// This command must always be answered with `readyok` and can be sent also when the engine is calculating in which case the engine should also immediately answer with readyok without stopping the search.
+    IsReady,
+
+    /// This is sent to the engine when the user wants to change the internal parameters of the engine.
+    SetOption(SetOptionCommand),
+
+    /// This is the command to try to register an engine or to tell the engine that registration will be done later.
+    ///
+    /// This command should always be sent if the engine has sent `registration error` at program startup.
+    Register(RegisterCommand),
+
+    /// This is sent to the engine when the next search (started with `position` and `go`) will be from a different game.
+    ///
+    /// This can be a new game the engine should play or a new game it should analyse but also the next position from a testsuite with positions only.
+    ///
+    /// If the GUI hasn't sent a `ucinewgame` before the first position command, the engine shouldn't expect any further `ucinewgame` commands as the GUI is probably not supporting the `ucinewgame` command.
+    /// So the engine should not rely on this command even though all new GUIs should support it.
+    ///
+    /// As the engine's reaction to `ucinewgame` can take some time the GUI should always send `isready` after `ucinewgame` to wait for the engine to finish its operation.
+    UciNewGame,
+
+    /// Set up the position described in `<fenstring>` on the internal board and play the moves on the internal chess board.
+    ///
+    /// If the game was played from the start position the string `startpos` will be sent.
+    ///
+    /// Note: no "new" command is needed.
+    /// However, if this position is from a different game than the last position sent to the engine, the GUI should have sent a `ucinewgame` inbetween.
+    Position(PositionCommand),
+
+    /// Start calculating on the current position set up with the `position` command.
+    Go(GoCommand),
+
+    /// Stop calculating as soon as possible.
+    ///
+    /// Don't forget the `bestmove` and possibly the `ponder` token when finishing the search.
+    Stop,
+
+    /// The user has played the expected move.
+    ///
+    /// This will be sent if the engine was told to ponder on the same move the user has played.
+    /// The engine should continue searching but switch from pondering to normal search.
+    PonderHit,
+
+    /// Quit the program as soon as possible.
+    Quit,
+}
+
+impl FromStr for GuiToEngineCommand {
+    type Err = CommandParseError;
+
+    /// Parse a command sent by the GUI.
+    ///
+    /// As required by the specification, unknown tokens before the command are ignored,
+    /// e.g. `joho debug on` is parsed as `debug on`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let all_tokens: Vec<_> = s.split_ascii_whitespace().collect();
+        let start = all_tokens
+            .iter()
+            .position(|token| COMMAND_NAMES.contains(token))
+            .ok_or(CommandParseError)?;
+
+        let tokens = &all_tokens[start..];
+        let s = &tokens.join(" ");
+
+        let command = match tokens {
+            ["uci", ..] => GuiToEngineCommand::Uci,
+            ["debug", "on", ..] => GuiToEngineCommand::Debug(true),
+            ["debug", "off", ..] => GuiToEngineCommand::Debug(false),
+            ["isready", ..] => GuiToEngineCommand::IsReady,
+            ["setoption", ..] => GuiToEngineCommand::SetOption(s.parse()?),
+            ["register", ..] => GuiToEngineCommand::Register(s.parse()?),
+            ["ucinewgame", ..] => GuiToEngineCommand::UciNewGame,
+            ["position", ..] => GuiToEngineCommand::Position(s.parse()?),
+            ["go", ..] => GuiToEngineCommand::Go(s.parse()?),
+            ["stop", ..] => GuiToEngineCommand::Stop,
+            ["ponderhit", ..] => GuiToEngineCommand::PonderHit,
+            ["quit", ..] => GuiToEngineCommand::Quit,
+            _ => return Err(CommandParseError),
+        };
+
+        Ok(command)
+    }
+}
+
+impl Display for GuiToEngineCommand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GuiToEngineCommand::Uci => write!(f, "uci"),
+            GuiToEngineCommand::Debug(true) => write!(f, "debug on"),
+            GuiToEngineCommand::Debug(false) => write!(f, "debug off"),
+            GuiToEngineCommand::IsReady => write!(f, "isready"),
+            GuiToEngineCommand::SetOption(set_option) => write!(f, "{set_option}"),
+            GuiToEngineCommand::Register(register) => write!(f, "{register}"),
+            GuiToEngineCommand::UciNewGame => write!(f, "ucinewgame"),
+            GuiToEngineCommand::Position(position) => write!(f, "{position}"),
+            GuiToEngineCommand::Go(go) => write!(f, "{go}"),
+            GuiToEngineCommand::Stop => write!(f, "stop"),
+            GuiToEngineCommand::PonderHit => write!(f, "ponderhit"),
+            GuiToEngineCommand::Quit => write!(f, "quit"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("uci", GuiToEngineCommand::Uci)]
+    #[case("debug on", GuiToEngineCommand::Debug(true))]
+    #[case("debug off", GuiToEngineCommand::Debug(false))]
+    #[case("isready", GuiToEngineCommand::IsReady)]
+    #[case(
+        "setoption name Hash value 32",
+        GuiToEngineCommand::SetOption(SetOptionCommand::new("Hash", "32"))
+    )]
+    #[case("register later", GuiToEngineCommand::Register(RegisterCommand::Later))]
+    #[case("ucinewgame", GuiToEngineCommand::UciNewGame)]
+    #[case(
+        "position startpos moves e2e4",
+        GuiToEngineCommand::Position(PositionCommand::new_startpos(
+            "e2e4".parse::<crate::chess::MoveLine>().unwrap()
+        ))
+    )]
+    #[case(
+        "go depth 3",
+        GuiToEngineCommand::Go(GoCommand { depth: Some(3), ..Default::default() })
+    )]
+    #[case("stop", GuiToEngineCommand::Stop)]
+    #[case("ponderhit", GuiToEngineCommand::PonderHit)]
+    #[case("quit", GuiToEngineCommand::Quit)]
+    fn parse_and_format_command(#[case] input: &str, #[case] expected: GuiToEngineCommand) {
+        assert_eq!(input.parse(), Ok(expected.clone()));
+        assert_eq!(expected.to_string(), input);
+    }
+
+    #[rstest]
+    #[case("joho debug on", GuiToEngineCommand::Debug(true))]
+    #[case("  isready  ", GuiToEngineCommand::IsReady)]
+    #[case("xboard go depth 1", GuiToEngineCommand::Go(GoCommand { depth: Some(1), ..Default::default() }))]
+    fn parse_command_ok(#[case] input: &str, #[case] expected: GuiToEngineCommand) {
+        assert_eq!(input.parse(), Ok(expected));
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("joho")]
+    #[case("debug")]
+    #[case("debug maybe")]
+    #[case("setoption")]
+    #[case("position")]
+    fn parse_command_err(#[case] input: &str) {
+        assert!(input.parse::<GuiToEngineCommand>().is_err());
+    }
+}
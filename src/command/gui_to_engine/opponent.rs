@@ -0,0 +1,176 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::command::CommandParseError;
+
+/// A chess title of a player.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Title {
+    /// Grandmaster, `GM`.
+    Grandmaster,
+
+    /// International Master, `IM`.
+    InternationalMaster,
+
+    /// FIDE Master, `FM`.
+    FideMaster,
+
+    /// Woman Grandmaster, `WGM`.
+    WomanGrandmaster,
+
+    /// Woman International Master, `WIM`.
+    WomanInternationalMaster,
+}
+
+impl FromStr for Title {
+    type Err = CommandParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "GM" => Ok(Title::Grandmaster),
+            "IM" => Ok(Title::InternationalMaster),
+            "FM" => Ok(Title::FideMaster),
+            "WGM" => Ok(Title::WomanGrandmaster),
+            "WIM" => Ok(Title::WomanInternationalMaster),
+            _ => Err(CommandParseError),
+        }
+    }
+}
+
+impl Display for Title {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let output = match self {
+            Title::Grandmaster => "GM",
+            Title::InternationalMaster => "IM",
+            Title::FideMaster => "FM",
+            Title::WomanGrandmaster => "WGM",
+            Title::WomanInternationalMaster => "WIM",
+        };
+
+        write!(f, "{output}")
+    }
+}
+
+/// The opponent of the engine, sent as the value of the `UCI_Opponent` option.
+///
+/// The value has the form `<title> <elo> <computer|human> <name>`,
+/// where the title and the Elo can be `none`, e.g. `GM 2800 human Gary Kasparov`
+/// or `none none computer Shredder`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Opponent {
+    /// The title of the opponent, if any.
+    pub title: Option<Title>,
+
+    /// The Elo rating of the opponent, if known.
+    pub elo: Option<u32>,
+
+    /// Determines if the opponent is a computer instead of a human.
+    pub is_computer: bool,
+
+    /// The name of the opponent.
+    pub name: String,
+}
+
+impl FromStr for Opponent {
+    type Err = CommandParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens: Vec<_> = s.split_ascii_whitespace().collect();
+
+        let [title, elo, kind, name @ ..] = tokens.as_slice() else {
+            return Err(CommandParseError);
+        };
+
+        let title = match *title {
+            "none" => None,
+            title => Some(title.parse()?),
+        };
+
+        let elo = match *elo {
+            "none" => None,
+            elo => Some(elo.parse().map_err(|_| CommandParseError)?),
+        };
+
+        let is_computer = match *kind {
+            "computer" => true,
+            "human" => false,
+            _ => return Err(CommandParseError),
+        };
+
+        Ok(Opponent {
+            title,
+            elo,
+            is_computer,
+            name: name.join(" "),
+        })
+    }
+}
+
+impl Display for Opponent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.title {
+            Some(title) => write!(f, "{title}")?,
+            None => write!(f, "none")?,
+        }
+
+        match self.elo {
+            Some(elo) => write!(f, " {elo}")?,
+            None => write!(f, " none")?,
+        }
+
+        let kind = if self.is_computer {
+            "computer"
+        } else {
+            "human"
+        };
+        write!(f, " {kind} {}", self.name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(
+        "GM 2800 human Gary Kasparov",
+        Opponent {
+            title: Some(Title::Grandmaster),
+            elo: Some(2800),
+            is_computer: false,
+            name: "Gary Kasparov".to_string(),
+        }
+    )]
+    #[case(
+        "none none computer Shredder",
+        Opponent {
+            title: None,
+            elo: None,
+            is_computer: true,
+            name: "Shredder".to_string(),
+        }
+    )]
+    #[case(
+        "WIM none human Jane Doe",
+        Opponent {
+            title: Some(Title::WomanInternationalMaster),
+            elo: None,
+            is_computer: false,
+            name: "Jane Doe".to_string(),
+        }
+    )]
+    fn parse_and_format_opponent(#[case] input: &str, #[case] expected: Opponent) {
+        assert_eq!(input.parse(), Ok(expected.clone()));
+        assert_eq!(expected.to_string(), input);
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("GM 2800")]
+    #[case("XX 2800 human Somebody")]
+    #[case("GM -5 human Somebody")]
+    #[case("GM 2800 alien Somebody")]
+    fn parse_opponent_err(#[case] input: &str) {
+        assert!(input.parse::<Opponent>().is_err());
+    }
+}
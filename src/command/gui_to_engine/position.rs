@@ -0,0 +1,151 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::{chess::MoveLine, command::CommandParseError};
+
+/// The position that the moves of a `position` command are played from.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum StartPosition {
+    /// The normal start position, denoted `startpos`.
+    StartPos,
+
+    /// The position given by the FEN string, denoted `fen <fenstring>`.
+    Fen(String),
+}
+
+/// Set up the position on the internal board and play the moves on it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PositionCommand {
+    /// The position to start from.
+    pub start: StartPosition,
+
+    /// The moves to play from the start position.
+    pub moves: MoveLine,
+}
+
+impl PositionCommand {
+    /// Create a new position command starting from the normal start position.
+    pub fn new_startpos<L>(moves: L) -> Self
+    where
+        L: Into<MoveLine>,
+    {
+        Self {
+            start: StartPosition::StartPos,
+            moves: moves.into(),
+        }
+    }
+
+    /// Create a new position command starting from the position given by the FEN string.
+    pub fn new_fen<S, L>(fen: S, moves: L) -> Self
+    where
+        S: Into<String>,
+        L: Into<MoveLine>,
+    {
+        Self {
+            start: StartPosition::Fen(fen.into()),
+            moves: moves.into(),
+        }
+    }
+}
+
+impl FromStr for PositionCommand {
+    type Err = CommandParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens: Vec<_> = s.split_ascii_whitespace().collect();
+
+        let ["position", rest @ ..] = tokens.as_slice() else {
+            return Err(CommandParseError);
+        };
+
+        let moves_index = rest
+            .iter()
+            .position(|&token| token == "moves")
+            .unwrap_or(rest.len());
+        let (start, moves) = rest.split_at(moves_index);
+
+        let start = match start {
+            ["startpos"] => StartPosition::StartPos,
+            ["fen", fen @ ..] if !fen.is_empty() => StartPosition::Fen(fen.join(" ")),
+            _ => return Err(CommandParseError),
+        };
+
+        let moves = match moves {
+            [] => MoveLine::new(),
+            ["moves", moves @ ..] => moves
+                .join(" ")
+                .parse::<MoveLine>()
+                .map_err(|_| CommandParseError)?,
+            _ => unreachable!("The moves always start with the moves keyword"),
+        };
+
+        Ok(PositionCommand { start, moves })
+    }
+}
+
+impl Display for PositionCommand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut output = match &self.start {
+            StartPosition::StartPos => "position startpos".to_string(),
+            StartPosition::Fen(fen) => format!("position fen {fen}"),
+        };
+
+        if !self.moves.is_empty() {
+            output += &format!(" moves {}", self.moves);
+        }
+
+        write!(f, "{output}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("position startpos", PositionCommand::new_startpos(MoveLine::new()))]
+    #[case(
+        "position startpos moves e2e4 e7e5",
+        PositionCommand::new_startpos("e2e4 e7e5".parse::<MoveLine>().unwrap())
+    )]
+    #[case(
+        "position fen 8/8/8/8/8/8/8/K1k5 w - - 0 1",
+        PositionCommand::new_fen("8/8/8/8/8/8/8/K1k5 w - - 0 1", MoveLine::new())
+    )]
+    #[case(
+        "position fen 8/8/8/8/8/8/8/K1k5 w - - 0 1 moves a1a2",
+        PositionCommand::new_fen(
+            "8/8/8/8/8/8/8/K1k5 w - - 0 1",
+            "a1a2".parse::<MoveLine>().unwrap()
+        )
+    )]
+    fn parse_and_format_position(#[case] input: &str, #[case] expected: PositionCommand) {
+        assert_eq!(input.parse(), Ok(expected.clone()));
+        assert_eq!(expected.to_string(), input);
+    }
+
+    #[rstest]
+    #[case(
+        "position startpos moves",
+        PositionCommand::new_startpos(MoveLine::new())
+    )]
+    #[case(
+        "position  startpos  moves  P@e4",
+        PositionCommand::new_startpos("P@e4".parse::<MoveLine>().unwrap())
+    )]
+    fn parse_position_ok(#[case] input: &str, #[case] expected: PositionCommand) {
+        assert_eq!(input.parse(), Ok(expected));
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("position")]
+    #[case("position fen")]
+    #[case("position fen moves e2e4")]
+    #[case("position startpos e2e4")]
+    #[case("position startpos moves e2e9")]
+    #[case("go")]
+    fn parse_position_err(#[case] input: &str) {
+        assert!(input.parse::<PositionCommand>().is_err());
+    }
+}
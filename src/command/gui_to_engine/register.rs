@@ -0,0 +1,100 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::command::CommandParseError;
+
+/// Try to register an engine or tell the engine that registration will be done later.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RegisterCommand {
+    /// The user doesn't want to register the engine now.
+    Later,
+
+    /// Register the engine with the given name and code.
+    Register {
+        /// The name of the user.
+        name: String,
+
+        /// The registration code.
+        code: String,
+    },
+}
+
+impl RegisterCommand {
+    /// Create a new command to register the engine with the given name and code.
+    pub fn new<N, C>(name: N, code: C) -> Self
+    where
+        N: Into<String>,
+        C: Into<String>,
+    {
+        RegisterCommand::Register {
+            name: name.into(),
+            code: code.into(),
+        }
+    }
+}
+
+impl FromStr for RegisterCommand {
+    type Err = CommandParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens: Vec<_> = s.split_ascii_whitespace().collect();
+
+        match tokens.as_slice() {
+            ["register", "later"] => Ok(RegisterCommand::Later),
+            ["register", "name", rest @ ..] => {
+                let code_index = rest
+                    .iter()
+                    .position(|&token| token == "code")
+                    .ok_or(CommandParseError)?;
+
+                let name = rest[..code_index].join(" ");
+                let code = rest[code_index + 1..].join(" ");
+
+                if name.is_empty() || code.is_empty() {
+                    return Err(CommandParseError);
+                }
+
+                Ok(RegisterCommand::Register { name, code })
+            }
+            _ => Err(CommandParseError),
+        }
+    }
+}
+
+impl Display for RegisterCommand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RegisterCommand::Later => write!(f, "register later"),
+            RegisterCommand::Register { name, code } => {
+                write!(f, "register name {name} code {code}")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("register later", RegisterCommand::Later)]
+    #[case(
+        "register name Stefan MK code 4359874324",
+        RegisterCommand::new("Stefan MK", "4359874324")
+    )]
+    fn parse_and_format_register(#[case] input: &str, #[case] expected: RegisterCommand) {
+        assert_eq!(input.parse(), Ok(expected.clone()));
+        assert_eq!(expected.to_string(), input);
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("register")]
+    #[case("register now")]
+    #[case("register name Stefan")]
+    #[case("register name code 123")]
+    #[case("register name Stefan code")]
+    fn parse_register_err(#[case] input: &str) {
+        assert!(input.parse::<RegisterCommand>().is_err());
+    }
+}
@@ -0,0 +1,215 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::{chess::Variant, command::CommandParseError};
+
+use super::Opponent;
+
+/// Change the value of an engine option.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SetOptionCommand {
+    /// The name of the option.
+    pub name: String,
+
+    /// The new value of the option.
+    ///
+    /// This is `None` for buttons.
+    pub value: Option<String>,
+}
+
+impl SetOptionCommand {
+    /// Create a new command to set the option to the given value.
+    pub fn new<N, V>(name: N, value: V) -> Self
+    where
+        N: Into<String>,
+        V: Into<String>,
+    {
+        Self {
+            name: name.into(),
+            value: Some(value.into()),
+        }
+    }
+
+    /// Create a new command to press the button with the given name.
+    pub fn new_button<N>(name: N) -> Self
+    where
+        N: Into<String>,
+    {
+        Self {
+            name: name.into(),
+            value: None,
+        }
+    }
+}
+
+impl SetOptionCommand {
+    /// Determines if this command sets the option with the given name.
+    ///
+    /// Like in the UCI protocol, the names are not case-sensitive.
+    pub fn is(&self, name: &str) -> bool {
+        self.name.eq_ignore_ascii_case(name)
+    }
+
+    /// The value of a checkbox, `true` or `false`.
+    pub fn check_value(&self) -> Option<bool> {
+        match self.value.as_deref()?.trim() {
+            value if value.eq_ignore_ascii_case("true") => Some(true),
+            value if value.eq_ignore_ascii_case("false") => Some(false),
+            _ => None,
+        }
+    }
+
+    /// The value of a spin wheel.
+    pub fn spin_value(&self) -> Option<i64> {
+        self.value.as_deref()?.trim().parse().ok()
+    }
+
+    /// The value of a text field, where `<empty>` is the empty string.
+    pub fn string_value(&self) -> Option<&str> {
+        match self.value.as_deref() {
+            Some("<empty>") | None => Some(""),
+            Some(value) => Some(value),
+        }
+    }
+
+    /// The value of the `UCI_Opponent` option.
+    pub fn opponent(&self) -> Option<Opponent> {
+        self.value.as_deref()?.parse().ok()
+    }
+
+    /// The value of the `UCI_Variant` option.
+    pub fn variant(&self) -> Option<Variant> {
+        self.value.as_deref()?.trim().parse().ok()
+    }
+}
+
+impl FromStr for SetOptionCommand {
+    type Err = CommandParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens: Vec<_> = s.split_ascii_whitespace().collect();
+
+        let ["setoption", "name", rest @ ..] = tokens.as_slice() else {
+            return Err(CommandParseError);
+        };
+
+        let (name, value) = match rest.iter().position(|&token| token == "value") {
+            Some(value_index) => (
+                rest[..value_index].join(" "),
+                Some(rest[value_index + 1..].join(" ")),
+            ),
+            None => (rest.join(" "), None),
+        };
+
+        if name.is_empty() {
+            return Err(CommandParseError);
+        }
+
+        Ok(SetOptionCommand { name, value })
+    }
+}
+
+impl Display for SetOptionCommand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.value {
+            Some(value) => write!(f, "setoption name {} value {value}", self.name),
+            None => write!(f, "setoption name {}", self.name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("setoption name Hash value 128", SetOptionCommand::new("Hash", "128"))]
+    #[case(
+        "setoption name Nalimov Path value c:\\chess\\tb\\4 c:\\chess\\tb\\5",
+        SetOptionCommand::new("Nalimov Path", "c:\\chess\\tb\\4 c:\\chess\\tb\\5")
+    )]
+    #[case(
+        "setoption name Clear Hash",
+        SetOptionCommand::new_button("Clear Hash")
+    )]
+    #[case(
+        "setoption name SyzygyPath value ",
+        SetOptionCommand::new("SyzygyPath", "")
+    )]
+    fn parse_set_option_ok(#[case] input: &str, #[case] expected: SetOptionCommand) {
+        assert_eq!(input.parse(), Ok(expected));
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("setoption")]
+    #[case("setoption name")]
+    #[case("setoption name value 3")]
+    #[case("setoption Hash value 3")]
+    fn parse_set_option_err(#[case] input: &str) {
+        assert!(input.parse::<SetOptionCommand>().is_err());
+    }
+
+    #[rstest]
+    #[case(SetOptionCommand::new("Hash", "128"), "setoption name Hash value 128")]
+    #[case(
+        SetOptionCommand::new_button("Clear Hash"),
+        "setoption name Clear Hash"
+    )]
+    fn format_set_option(#[case] input: SetOptionCommand, #[case] expected: String) {
+        assert_eq!(input.to_string(), expected);
+    }
+
+    #[rstest]
+    #[case("setoption name UCI_ShowWDL value true", Some(true))]
+    #[case("setoption name Ponder value FALSE", Some(false))]
+    #[case("setoption name Ponder value yes", None)]
+    #[case("setoption name Clear Hash", None)]
+    fn set_option_check_value(#[case] input: SetOptionCommand, #[case] expected: Option<bool>) {
+        assert_eq!(input.check_value(), expected);
+    }
+
+    #[rstest]
+    #[case("setoption name Threads value 8", Some(8))]
+    #[case("setoption name UCI_Elo value -1", Some(-1))]
+    #[case("setoption name Threads value many", None)]
+    fn set_option_spin_value(#[case] input: SetOptionCommand, #[case] expected: Option<i64>) {
+        assert_eq!(input.spin_value(), expected);
+    }
+
+    #[rstest]
+    #[case(
+        "setoption name UCI_Variant value crazyhouse",
+        Some(Variant::Crazyhouse)
+    )]
+    #[case("setoption name UCI_Variant value 3check", Some(Variant::ThreeCheck))]
+    #[case("setoption name UCI_Variant value shogi", None)]
+    fn set_option_variant_value(
+        #[case] input: SetOptionCommand,
+        #[case] expected: Option<Variant>,
+    ) {
+        assert_eq!(input.variant(), expected);
+    }
+
+    #[test]
+    fn set_option_string_and_opponent_value() {
+        let syzygy = "setoption name SyzygyPath value <empty>"
+            .parse::<SetOptionCommand>()
+            .unwrap();
+        assert_eq!(syzygy.string_value(), Some(""));
+        assert!(syzygy.is("syzygypath"));
+
+        let opponent = "setoption name UCI_Opponent value none 3500 computer Stockfish 17"
+            .parse::<SetOptionCommand>()
+            .unwrap();
+        assert_eq!(
+            opponent.opponent(),
+            Some(Opponent {
+                title: None,
+                elo: Some(3500),
+                is_computer: true,
+                name: "Stockfish 17".to_string(),
+            })
+        );
+    }
+}
@@ -0,0 +1,631 @@
+use std::{fmt::Display, io, str::FromStr, time::Instant};
+
+use crate::{
+    chess::{File, HumanFormat, IllegalMove, MoveLine, Position, Rank, Square, Variant},
+    command::{
+        gui_to_engine::{GoCommand, GuiToEngineCommand, PositionCommand, StartPosition},
+        CommandParseError,
+    },
+};
+
+use super::{SearchContext, UciEngine, UciWriter};
+use crate::uci_debug;
+
+/// The depth searched by `bench`, if no depth is given.
+pub const DEFAULT_BENCH_DEPTH: u32 = 12;
+
+/// The positions searched by `bench`.
+pub const BENCH_POSITIONS: [&str; 12] = [
+    "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+    "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 10",
+    "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 11",
+    "4rrk1/pp1n3p/3q2pQ/2p1pb2/2PP4/2P3N1/P2B2PP/4RRK1 b - - 7 19",
+    "r3r1k1/2p2ppp/p1p1bn2/8/1q2P3/2NPQN2/PPP3PP/R4RK1 b - - 2 15",
+    "r1bbk1nr/pp3p1p/2n5/1N4p1/2Np1B2/8/PPP2PPP/2KR1B1R w kq - 0 13",
+    "r1bq1rk1/ppp1nppp/4n3/3p3Q/3P4/1BP1B3/PP1N2PP/R4RK1 w - - 1 16",
+    "4r1k1/r1q2ppp/ppp2n2/4P3/5Rb1/1N1BQ3/PPP3PP/R5K1 w - - 1 17",
+    "2rqkb1r/ppp2p2/2npb1p1/1N1Nn2p/2P1PP2/8/PP2B1PP/R1BQK2R b KQ - 0 11",
+    "r1bq1r1k/b1p1npp1/p2p3p/1p6/3PP3/1B2NN2/PP3PPP/R2Q1RK1 w - - 1 16",
+    "6k1/6p1/6Pp/ppp5/3pn2P/1P3K2/1PP2P2/3N4 b - - 0 1",
+    "3b4/5kp1/1p1p1p1p/pP1PpP1P/P1P1P3/3KN3/8/8 w - - 0 1",
+];
+
+/// A line received from the GUI or from a user typing in a terminal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Input {
+    /// A command of the UCI protocol.
+    Uci(GuiToEngineCommand),
+
+    /// A non-standard command, e.g. to debug the engine.
+    Custom(CustomCommand),
+}
+
+impl Display for Input {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Input::Uci(command) => write!(f, "{command}"),
+            Input::Custom(command) => write!(f, "{command}"),
+        }
+    }
+}
+
+/// A command that is not part of the UCI protocol, but common among engines.
+///
+/// The built-in commands `d`, `flip` and `go perft` are played on the [`Position`] set up by
+/// the last `position` command, with the variant selected by `UCI_Variant`.
+/// Only `eval` needs the engine, see [`UciEngine::evaluate`].
+/// All other lines are passed to [`UciEngine::custom_command`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CustomCommand {
+    /// `d`: Show the current position as diagram and FEN.
+    Display,
+
+    /// `eval`: Show the static evaluation of the current position.
+    Eval,
+
+    /// `flip`: Mirror the current position, swapping the colors.
+    Flip,
+
+    /// `go perft <depth>`: Count the leaf nodes of the move tree up to the given depth,
+    /// for every legal move.
+    Perft(u32),
+
+    /// `bench [depth]`: Search a fixed list of positions and report the nodes and speed.
+    ///
+    /// Without depth, [`DEFAULT_BENCH_DEPTH`] is used.
+    Bench(Option<u32>),
+
+    /// A built-in command with invalid arguments, e.g. `go perft` without depth,
+    /// which is reported to the GUI.
+    Invalid(String),
+
+    /// Any other line, which the engine may handle on its own.
+    Other(String),
+}
+
+impl FromStr for CustomCommand {
+    type Err = CommandParseError;
+
+    /// Parse one of the built-in commands.
+    ///
+    /// [`CustomCommand::Invalid`] and [`CustomCommand::Other`] are never returned,
+    /// they are used for the lines that can't be parsed as built-in command.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens: Vec<_> = s.split_ascii_whitespace().collect();
+
+        let command = match tokens.as_slice() {
+            ["d"] => CustomCommand::Display,
+            ["eval"] => CustomCommand::Eval,
+            ["flip"] => CustomCommand::Flip,
+            ["go", "perft", depth] => match depth.parse() {
+                Ok(depth @ 1..) => CustomCommand::Perft(depth),
+                _ => return Err(CommandParseError),
+            },
+            ["bench"] => CustomCommand::Bench(None),
+            ["bench", depth] => {
+                CustomCommand::Bench(Some(depth.parse().map_err(|_| CommandParseError)?))
+            }
+            _ => return Err(CommandParseError),
+        };
+
+        Ok(command)
+    }
+}
+
+impl Display for CustomCommand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CustomCommand::Display => write!(f, "d"),
+            CustomCommand::Eval => write!(f, "eval"),
+            CustomCommand::Flip => write!(f, "flip"),
+            CustomCommand::Perft(depth) => write!(f, "go perft {depth}"),
+            CustomCommand::Bench(None) => write!(f, "bench"),
+            CustomCommand::Bench(Some(depth)) => write!(f, "bench {depth}"),
+            CustomCommand::Invalid(line) | CustomCommand::Other(line) => write!(f, "{line}"),
+        }
+    }
+}
+
+/// Parse a line as built-in, UCI or other custom command.
+///
+/// The built-in commands are parsed first, because `go perft` is also a valid `go` command.
+/// Any line starting with `go perft` is a built-in command, even with an invalid depth,
+/// so that it doesn't start a search.
+pub(crate) fn parse_input(line: &str) -> Input {
+    if let Ok(command) = line.parse() {
+        return Input::Custom(command);
+    }
+
+    if line.split_ascii_whitespace().take(2).eq(["go", "perft"]) {
+        return Input::Custom(CustomCommand::Invalid(line.to_string()));
+    }
+
+    match line.parse() {
+        Ok(command) => Input::Uci(command),
+        Err(_) => Input::Custom(CustomCommand::Other(line.to_string())),
+    }
+}
+
+/// The position of the last `position` command, used by the non-standard commands.
+pub(crate) struct CurrentPosition {
+    /// The last `position` command, or the position set up by `flip`.
+    ///
+    /// It's sent to the engine again after `bench`.
+    pub command: PositionCommand,
+
+    /// The position set up on a board of the variant, or the reason why it couldn't be set up.
+    pub board: Result<Position, String>,
+}
+
+impl CurrentPosition {
+    /// The start position of standard chess, until the GUI sends `position`.
+    pub fn new() -> Self {
+        Self {
+            command: PositionCommand::new_startpos(MoveLine::new()),
+            board: Ok(Position::new(Variant::Standard)),
+        }
+    }
+
+    /// Set up the position of the `position` command on a board of the variant.
+    pub fn set(&mut self, variant: Variant, command: PositionCommand) {
+        self.board = set_up_position(variant, &command);
+        self.command = command;
+    }
+}
+
+/// Set up the position of the `position` command on a board of the variant.
+///
+/// Returns the reason as error, if the FEN or a move is invalid.
+fn set_up_position(variant: Variant, position: &PositionCommand) -> Result<Position, String> {
+    let mut board = match &position.start {
+        StartPosition::StartPos => Position::new(variant),
+        StartPosition::Fen(fen) => Position::from_fen(variant, fen)
+            .map_err(|_| format!("invalid {variant} position {fen}"))?,
+    };
+
+    for mv in position.moves.iter() {
+        board.play(mv).map_err(|IllegalMove { mv }| {
+            format!("illegal move {mv} in {variant} position {board}")
+        })?;
+    }
+
+    Ok(board)
+}
+
+/// Handle a non-standard command.
+///
+/// The position is the one set up by the last `position` command
+/// with the variant selected by `UCI_Variant`.
+/// Commands that the engine doesn't support are reported as `info string`.
+pub(crate) fn handle<E>(
+    engine: &mut E,
+    writer: &UciWriter,
+    variant: Variant,
+    position: &mut CurrentPosition,
+    command: CustomCommand,
+) -> io::Result<()>
+where
+    E: UciEngine,
+{
+    let board = match (&command, &position.board) {
+        (CustomCommand::Display | CustomCommand::Flip | CustomCommand::Perft(_), Err(error)) => {
+            return writer.send_string(format!("{command} needs a valid position: {error}"));
+        }
+        (_, board) => board.as_ref().ok(),
+    };
+
+    match &command {
+        CustomCommand::Display => {
+            let Some(board) = board else {
+                return Ok(());
+            };
+
+            for line in diagram(board) {
+                writer.send_line(&line)?;
+            }
+
+            writer.send_line("")?;
+            writer.send_line(&format!("Fen: {board}"))?;
+        }
+        CustomCommand::Eval => {
+            let Some(eval) = engine.evaluate() else {
+                return writer.send_string(format!("{command} is not supported by the engine"));
+            };
+
+            let line = match board {
+                Some(board) => format!(
+                    "Final evaluation: {} (white side)",
+                    HumanFormat::new()
+                        .from_white_pov(board.side_to_move())
+                        .format(eval)
+                ),
+                None => format!(
+                    "Final evaluation: {} (side to move)",
+                    HumanFormat::new().format(eval)
+                ),
+            };
+
+            writer.send_line(&line)?;
+        }
+        CustomCommand::Flip => {
+            let Some(flipped) = board.map(Position::flipped) else {
+                return Ok(());
+            };
+
+            position.command = PositionCommand::new_fen(flipped.to_string(), MoveLine::new());
+            position.board = Ok(flipped);
+            engine.position(&position.command);
+        }
+        CustomCommand::Perft(depth) => {
+            let Some(board) = board else {
+                return Ok(());
+            };
+
+            let mut nodes = 0;
+
+            for mv in board.legal_moves() {
+                let mut child = board.clone();
+                child.play(mv).expect("The move is legal");
+
+                let child_nodes = child.perft(depth - 1);
+                writer.send_line(&format!("{mv}: {child_nodes}"))?;
+                nodes += child_nodes;
+            }
+
+            writer.send_line("")?;
+            writer.send_line(&format!("Nodes searched: {nodes}"))?;
+        }
+        CustomCommand::Bench(depth) => {
+            bench(
+                engine,
+                writer,
+                variant,
+                depth.unwrap_or(DEFAULT_BENCH_DEPTH),
+            )?;
+
+            // Continue with the position of the GUI
+            engine.position(&position.command);
+        }
+        CustomCommand::Invalid(line) => writer.send_string(format!("invalid command {line}"))?,
+        CustomCommand::Other(line) => {
+            if !engine.custom_command(line, writer) {
+                uci_debug!(writer, "ignored unknown command {line}");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Search all [`BENCH_POSITIONS`] to the given depth and report the nodes and speed, like `bench`.
+///
+/// The positions are sent to the engine in the FEN of the variant, e.g. with empty pockets
+/// in crazyhouse, and positions that are not valid in the variant are skipped.
+/// Every position is searched from a new game, so that the node count is reproducible.
+/// Afterwards, the engine is left in the last position.
+///
+/// This allows to run `bench` from the command line, e.g. after [`configure`](super::configure):
+///
+/// ```no_run
+/// # use universal_chess_interface::{chess::Variant, engine::{bench, UciEngine, UciWriter, DEFAULT_BENCH_DEPTH}};
+/// # fn main_with<E: UciEngine>(engine: &mut E) {
+/// let writer = UciWriter::new(std::io::stdout());
+/// bench(engine, &writer, Variant::Standard, DEFAULT_BENCH_DEPTH).unwrap();
+/// # }
+/// ```
+pub fn bench<E>(engine: &mut E, writer: &UciWriter, variant: Variant, depth: u32) -> io::Result<()>
+where
+    E: UciEngine,
+{
+    let go = GoCommand {
+        depth: Some(depth),
+        ..Default::default()
+    };
+    let start = Instant::now();
+    let mut nodes = 0;
+
+    for (index, fen) in BENCH_POSITIONS.iter().enumerate() {
+        let Ok(board) = Position::from_fen(variant, fen) else {
+            writer.send_string(format!("skipped invalid {variant} position {fen}"))?;
+            continue;
+        };
+
+        writer.send_line(&format!(
+            "Position: {}/{} ({board})",
+            index + 1,
+            BENCH_POSITIONS.len()
+        ))?;
+
+        engine.new_game();
+        engine.position(&PositionCommand::new_fen(
+            board.to_string(),
+            MoveLine::new(),
+        ));
+
+        let context = SearchContext::new(writer.clone());
+        context.run(engine, &go)?;
+        nodes += context.node_count_so_far().unwrap_or(0);
+    }
+
+    let elapsed = start.elapsed();
+    let nodes_per_second = (u128::from(nodes) * 1000) / elapsed.as_millis().max(1);
+
+    writer.send_line("")?;
+    writer.send_line("===========================")?;
+    writer.send_line(&format!("Total time (ms) : {}", elapsed.as_millis()))?;
+    writer.send_line(&format!("Nodes searched  : {nodes}"))?;
+    writer.send_line(&format!("Nodes/second    : {nodes_per_second}"))
+}
+
+/// Draw the board as diagram, with White at the bottom.
+fn diagram(board: &Position) -> Vec<String> {
+    const SEPARATOR: &str = " +---+---+---+---+---+---+---+---+";
+
+    let mut lines = vec![SEPARATOR.to_string()];
+
+    for rank in (0..8).rev().filter_map(Rank::from_index) {
+        let squares: String = (0..8)
+            .filter_map(File::from_index)
+            .map(|file| match board.piece_at(Square::new(file, rank)) {
+                Some(piece) => format!(" {piece} |"),
+                None => "   |".to_string(),
+            })
+            .collect();
+
+        lines.push(format!(" |{squares} {rank}"));
+        lines.push(SEPARATOR.to_string());
+    }
+
+    lines.push("   a   b   c   d   e   f   g   h".to_string());
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        chess::Evaluation,
+        engine::{
+            run,
+            test_utils::{run_engine, SharedBuffer, TestEngine},
+        },
+    };
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("d", CustomCommand::Display)]
+    #[case("eval", CustomCommand::Eval)]
+    #[case("flip", CustomCommand::Flip)]
+    #[case("go perft 5", CustomCommand::Perft(5))]
+    #[case("bench", CustomCommand::Bench(None))]
+    #[case("bench 16", CustomCommand::Bench(Some(16)))]
+    fn parse_and_format_custom_command(#[case] input: &str, #[case] expected: CustomCommand) {
+        assert_eq!(input.parse(), Ok(expected.clone()));
+        assert_eq!(expected.to_string(), input);
+    }
+
+    #[rstest]
+    #[case("go perft", Input::Custom(CustomCommand::Invalid("go perft".to_string())))]
+    #[case("go perft 0", Input::Custom(CustomCommand::Invalid("go perft 0".to_string())))]
+    #[case("go perft x", Input::Custom(CustomCommand::Invalid("go perft x".to_string())))]
+    #[case("go perft 2 3", Input::Custom(CustomCommand::Invalid("go perft 2 3".to_string())))]
+    #[case("go depth 3", Input::Uci(GuiToEngineCommand::Go(GoCommand { depth: Some(3), ..Default::default() })))]
+    #[case("joho", Input::Custom(CustomCommand::Other("joho".to_string())))]
+    fn parse_uci_and_other_input(#[case] input: &str, #[case] expected: Input) {
+        assert_eq!(parse_input(input), expected);
+    }
+
+    #[test]
+    fn bench_positions_are_valid() {
+        for fen in BENCH_POSITIONS {
+            assert!(
+                Position::from_fen(Variant::Standard, fen).is_ok(),
+                "invalid bench position {fen}"
+            );
+        }
+    }
+
+    #[test]
+    fn display_position() {
+        let (_, output) = run_engine(
+            TestEngine::default(),
+            "position fen 4k3/8/8/8/8/8/3P4/4K2R w K - 0 1\nd\n",
+        );
+
+        assert_eq!(
+            output,
+            [
+                " +---+---+---+---+---+---+---+---+",
+                " |   |   |   |   | k |   |   |   | 8",
+                " +---+---+---+---+---+---+---+---+",
+                " |   |   |   |   |   |   |   |   | 7",
+                " +---+---+---+---+---+---+---+---+",
+                " |   |   |   |   |   |   |   |   | 6",
+                " +---+---+---+---+---+---+---+---+",
+                " |   |   |   |   |   |   |   |   | 5",
+                " +---+---+---+---+---+---+---+---+",
+                " |   |   |   |   |   |   |   |   | 4",
+                " +---+---+---+---+---+---+---+---+",
+                " |   |   |   |   |   |   |   |   | 3",
+                " +---+---+---+---+---+---+---+---+",
+                " |   |   |   | P |   |   |   |   | 2",
+                " +---+---+---+---+---+---+---+---+",
+                " |   |   |   |   | K |   |   | R | 1",
+                " +---+---+---+---+---+---+---+---+",
+                "   a   b   c   d   e   f   g   h",
+                "",
+                "Fen: 4k3/8/8/8/8/8/3P4/4K2R w K - 0 1",
+            ]
+        );
+    }
+
+    #[test]
+    fn evaluate_and_flip_position() {
+        let engine = TestEngine {
+            eval: Some(Evaluation::Centipawns(-37)),
+            ..Default::default()
+        };
+
+        let (engine, output) = run_engine(
+            engine,
+            "position fen 4k3/8/8/8/8/8/3P4/4K3 b - - 0 1\neval\nflip\neval\n",
+        );
+
+        assert_eq!(
+            output,
+            [
+                "Final evaluation: +0.37 (white side)",
+                "Final evaluation: -0.37 (white side)",
+            ]
+        );
+        assert_eq!(
+            engine.received,
+            [
+                "position fen 4k3/8/8/8/8/8/3P4/4K3 b - - 0 1",
+                "position fen 4k3/3p4/8/8/8/8/8/4K3 w - - 0 1",
+            ]
+        );
+    }
+
+    #[test]
+    fn divide_perft() {
+        let (_, output) = run_engine(
+            TestEngine::default(),
+            "position fen 4k3/8/8/8/8/8/8/4K2R w K - 0 1 moves e1f1 e8d8\ngo perft 2\n",
+        );
+
+        assert_eq!(
+            output,
+            [
+                "f1f2: 5",
+                "f1g2: 5",
+                "f1g1: 5",
+                "f1e1: 5",
+                "f1e2: 5",
+                "h1h2: 5",
+                "h1h3: 5",
+                "h1h4: 5",
+                "h1h5: 5",
+                "h1h6: 5",
+                "h1h7: 2",
+                "h1h8: 3",
+                "h1g1: 5",
+                "",
+                "Nodes searched: 60",
+            ]
+        );
+    }
+
+    #[test]
+    fn divide_perft_of_variant() {
+        let (_, output) = run_engine(
+            TestEngine::default(),
+            "setoption name UCI_Variant value crazyhouse\n\
+            position fen k7/8/8/8/8/8/8/K7[N] w - - 0 1\n\
+            go perft 1\n",
+        );
+
+        assert_eq!(output.len(), 3 + 62 + 2);
+        assert!(output.contains(&"N@h8: 1".to_string()));
+        assert_eq!(output.last().unwrap(), "Nodes searched: 65");
+    }
+
+    #[test]
+    fn report_invalid_commands_and_positions() {
+        let (engine, output) = run_engine(
+            TestEngine::default(),
+            "go perft\nposition startpos moves e2e5\nd\nflip\ngo perft 1\neval\n",
+        );
+
+        assert_eq!(
+            output,
+            [
+                "info string invalid command go perft",
+                "info string d needs a valid position: illegal move e2e5 in chess position \
+                rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+                "info string flip needs a valid position: illegal move e2e5 in chess position \
+                rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+                "info string go perft 1 needs a valid position: illegal move e2e5 in chess position \
+                rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+                "info string eval is not supported by the engine",
+            ]
+        );
+        assert_eq!(engine.received, ["position startpos moves e2e5"]);
+    }
+
+    #[test]
+    fn pass_other_commands_to_engine() {
+        let (engine, output) = run_engine(TestEngine::default(), "debug on\nhello\njoho\n");
+
+        assert_eq!(
+            output,
+            [
+                "info string debug mode on",
+                "info string received hello",
+                "hello there",
+                "info string received joho",
+                "info string ignored unknown command joho",
+            ]
+        );
+        assert_eq!(engine.received, ["hello"]);
+    }
+
+    #[test]
+    fn bench_all_positions() {
+        let mut engine = TestEngine {
+            node_count: Some(100),
+            ..Default::default()
+        };
+        let output = SharedBuffer::default();
+        run(
+            &mut engine,
+            "position startpos moves e2e4\nbench 3\n".as_bytes(),
+            output.clone(),
+            None,
+        )
+        .unwrap();
+
+        let output = output.lines();
+        let count = |prefix: &str| {
+            output
+                .iter()
+                .filter(|line| line.starts_with(prefix))
+                .count()
+        };
+
+        assert_eq!(count("Position: "), BENCH_POSITIONS.len());
+        assert_eq!(count("bestmove "), BENCH_POSITIONS.len());
+        assert!(output.contains(&"Nodes searched  : 1200".to_string()));
+        assert_eq!(
+            engine
+                .received
+                .iter()
+                .filter(|go| *go == "go depth 3")
+                .count(),
+            BENCH_POSITIONS.len()
+        );
+        assert_eq!(
+            engine.received.last().unwrap(),
+            "position startpos moves e2e4"
+        );
+    }
+
+    #[test]
+    fn bench_positions_of_variant() {
+        let (engine, output) = run_engine(
+            TestEngine::default(),
+            "setoption name UCI_Variant value crazyhouse\nbench 1\n",
+        );
+
+        assert_eq!(
+            output[0],
+            "Position: 1/12 (rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR[] w KQkq - 0 1)"
+        );
+        assert!(!output.iter().any(|line| line.starts_with("info string")));
+        assert_eq!(
+            engine.received[2],
+            "position fen rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR[] w KQkq - 0 1"
+        );
+        assert_eq!(engine.received.last().unwrap(), "position startpos");
+    }
+}
@@ -0,0 +1,24 @@
+/// Send debug information to the GUI as `info string`, but only in debug mode.
+///
+/// The first argument is a [`SearchContext`](crate::engine::SearchContext)
+/// or a [`UciWriter`](crate::engine::UciWriter), the rest are passed to [`format!`].
+/// The message is not even formatted if the GUI hasn't sent `debug on`,
+/// so this can be used in hot code paths.
+/// Errors while sending are ignored.
+///
+/// ```
+/// use universal_chess_interface::{engine::UciWriter, uci_debug};
+///
+/// let writer = UciWriter::new(std::io::sink());
+/// uci_debug!(writer, "hash table resized to {} MB", 256);
+/// ```
+#[macro_export]
+macro_rules! uci_debug {
+    ($target:expr, $($arg:tt)*) => {{
+        let target = &$target;
+
+        if target.is_debug() {
+            target.send_string(::std::format!($($arg)*)).ok();
+        }
+    }};
+}
@@ -0,0 +1,185 @@
+use std::time::Duration;
+
+use crate::{chess::Color, command::gui_to_engine::GoCommand};
+
+use super::TimeBudget;
+
+/// The limits of a search, combining all parameters of the `go` command.
+///
+/// The engine should check [`SearchLimits::should_stop`] during the search and
+/// [`SearchLimits::may_start_iteration`] before every iteration of iterative deepening.
+///
+/// In `infinite` and `ponder` mode, the limits never stop the search.
+/// The engine has to keep searching until `stop` is received, see [`SearchContext::should_stop`],
+/// or until `ponderhit` is received, see [`SearchLimits::ponder_hit`].
+///
+/// [`SearchContext::should_stop`]: super::SearchContext::should_stop
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SearchLimits {
+    /// The maximum depth to search, in plies.
+    depth: Option<u32>,
+
+    /// The maximum number of nodes to search.
+    nodes: Option<u64>,
+
+    /// The time budget of the search.
+    time: Option<TimeBudget>,
+
+    /// Search until `stop` is received.
+    infinite: bool,
+
+    /// Search until `stop` or `ponderhit` is received.
+    ponder: bool,
+}
+
+impl SearchLimits {
+    /// Create the limits for the `go` command, with the time budget determined by the engine.
+    ///
+    /// A search for a mate in `n` moves is limited to a depth of `2n - 1` plies.
+    pub fn new(go: &GoCommand, time: Option<TimeBudget>) -> Self {
+        let mate_depth = go.mate.map(|moves| (2 * moves).saturating_sub(1));
+        let depth = match (go.depth, mate_depth) {
+            (Some(depth), Some(mate_depth)) => Some(depth.min(mate_depth)),
+            (depth, mate_depth) => depth.or(mate_depth),
+        };
+
+        Self {
+            depth,
+            nodes: go.nodes,
+            time,
+            infinite: go.infinite,
+            ponder: go.ponder,
+        }
+    }
+
+    /// Create the limits for the `go` command, with the time budget from
+    /// [`TimeBudget::from_go`].
+    pub fn from_go(go: &GoCommand, side_to_move: Color, move_overhead: Duration) -> Self {
+        Self::new(go, TimeBudget::from_go(go, side_to_move, move_overhead))
+    }
+
+    /// The maximum depth to search, in plies.
+    pub fn depth(&self) -> Option<u32> {
+        self.depth
+    }
+
+    /// The maximum number of nodes to search.
+    pub fn nodes(&self) -> Option<u64> {
+        self.nodes
+    }
+
+    /// The time budget of the search.
+    pub fn time(&self) -> Option<TimeBudget> {
+        self.time
+    }
+
+    /// Determines if the search only stops when `stop` or `ponderhit` is received.
+    pub fn is_unlimited(&self) -> bool {
+        self.infinite || self.ponder
+    }
+
+    /// The GUI has sent `ponderhit`, the limits apply from now on.
+    ///
+    /// The time budget is replaced, because the time is measured from the `ponderhit`.
+    pub fn ponder_hit(&mut self, time: Option<TimeBudget>) {
+        self.ponder = false;
+        self.time = time;
+    }
+
+    /// Determines if the search must stop now.
+    ///
+    /// `depth` is the depth of the last completed iteration, in plies,
+    /// `nodes` the number of nodes searched and `elapsed` the time spent on the search.
+    pub fn should_stop(&self, depth: u32, nodes: u64, elapsed: Duration) -> bool {
+        if self.is_unlimited() {
+            return false;
+        }
+
+        self.depth.is_some_and(|max_depth| depth >= max_depth)
+            || self.nodes.is_some_and(|max_nodes| nodes >= max_nodes)
+            || self.time.is_some_and(|time| elapsed >= time.hard)
+    }
+
+    /// Determines if the next iteration of iterative deepening may be started.
+    ///
+    /// The arguments are the same as for [`SearchLimits::should_stop`].
+    /// In addition, no new iteration is started after the soft time limit,
+    /// because it would likely not finish in time.
+    pub fn may_start_iteration(&self, depth: u32, nodes: u64, elapsed: Duration) -> bool {
+        if self.is_unlimited() {
+            return true;
+        }
+
+        !self.should_stop(depth, nodes, elapsed) && self.time.is_none_or(|time| elapsed < time.soft)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn limits(go: &str) -> SearchLimits {
+        SearchLimits::from_go(&go.parse().unwrap(), Color::White, Duration::ZERO)
+    }
+
+    #[rstest]
+    #[case("go depth 5", 4, 0, 0, false)]
+    #[case("go depth 5", 5, 0, 0, true)]
+    #[case("go nodes 1000", 20, 999, 0, false)]
+    #[case("go nodes 1000", 20, 1000, 0, true)]
+    #[case("go mate 3", 4, 0, 0, false)]
+    #[case("go mate 3", 5, 0, 0, true)]
+    #[case("go mate 3 depth 2", 2, 0, 0, true)]
+    #[case("go movetime 100", 20, 0, 99, false)]
+    #[case("go movetime 100", 1, 0, 100, true)]
+    #[case("go wtime 1000 movestogo 1 depth 10", 3, 0, 999, false)]
+    #[case("go wtime 1000 movestogo 1 depth 10", 3, 0, 1000, true)]
+    #[case("go", 100, 1_000_000, 100_000, false)]
+    #[case("go infinite depth 1 movetime 1", 100, 1_000_000, 100_000, false)]
+    #[case("go ponder wtime 1000 depth 1", 100, 1_000_000, 100_000, false)]
+    fn search_limits_should_stop(
+        #[case] go: &str,
+        #[case] depth: u32,
+        #[case] nodes: u64,
+        #[case] elapsed: u64,
+        #[case] expected: bool,
+    ) {
+        assert_eq!(
+            limits(go).should_stop(depth, nodes, Duration::from_millis(elapsed)),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case("go depth 5", 4, 0, true)]
+    #[case("go depth 5", 5, 0, false)]
+    #[case("go wtime 60000 movestogo 10", 8, 5999, true)]
+    #[case("go wtime 60000 movestogo 10", 8, 6000, false)]
+    #[case("go infinite", 1000, 1_000_000, true)]
+    fn search_limits_may_start_iteration(
+        #[case] go: &str,
+        #[case] depth: u32,
+        #[case] elapsed: u64,
+        #[case] expected: bool,
+    ) {
+        assert_eq!(
+            limits(go).may_start_iteration(depth, 0, Duration::from_millis(elapsed)),
+            expected
+        );
+    }
+
+    #[test]
+    fn search_limits_apply_after_ponder_hit() {
+        let go = "go ponder wtime 1000 movestogo 1".parse().unwrap();
+        let mut limits = SearchLimits::from_go(&go, Color::White, Duration::ZERO);
+        assert!(!limits.should_stop(1, 0, Duration::from_secs(10)));
+
+        let budget = TimeBudget::from_go(&go, Color::White, Duration::ZERO);
+        limits.ponder_hit(budget);
+
+        assert!(!limits.is_unlimited());
+        assert!(!limits.should_stop(1, 0, Duration::from_millis(999)));
+        assert!(limits.should_stop(1, 0, Duration::from_millis(1000)));
+    }
+}
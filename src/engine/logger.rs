@@ -0,0 +1,174 @@
+use std::sync::{Mutex, PoisonError};
+
+use log::{LevelFilter, Log, Metadata, Record, SetLoggerError};
+
+use super::UciWriter;
+
+/// The writer of the running engine loop, which the logger sends the records to.
+static WRITER: Mutex<Option<UciWriter>> = Mutex::new(None);
+
+/// Send the log records to the writer of the engine loop that has been started last.
+pub(crate) fn set_writer(writer: &UciWriter) {
+    *WRITER.lock().unwrap_or_else(PoisonError::into_inner) = Some(writer.clone());
+}
+
+/// A logger sending the records of the [`log`] crate to the GUI, as `info string`.
+///
+/// GUIs only show the output of the engine, so this makes logs visible in the engine window.
+/// The records are sent by the engine loop, e.g. [`run`](super::run), once it has been started.
+/// Records logged before are dropped.
+///
+/// Libraries using `tracing` can be bridged too, with the `log` feature of `tracing`.
+///
+/// ```
+/// use log::LevelFilter;
+/// use universal_chess_interface::engine::UciLogger;
+///
+/// UciLogger::new()
+///     .with_level(LevelFilter::Debug)
+///     .only_in_debug_mode()
+///     .init()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UciLogger {
+    /// The most verbose level that is sent.
+    level: LevelFilter,
+
+    /// Send the records only while the GUI has turned on the debug mode.
+    only_in_debug_mode: bool,
+}
+
+impl UciLogger {
+    /// Create a new logger sending all records up to the `Info` level.
+    pub const fn new() -> Self {
+        Self {
+            level: LevelFilter::Info,
+            only_in_debug_mode: false,
+        }
+    }
+
+    /// Set the most verbose level that is sent.
+    pub const fn with_level(mut self, level: LevelFilter) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// Send the records only while the GUI has turned on the debug mode with `debug on`.
+    pub const fn only_in_debug_mode(mut self) -> Self {
+        self.only_in_debug_mode = true;
+        self
+    }
+
+    /// Install the logger as global logger.
+    ///
+    /// Fails if another logger has already been installed.
+    pub fn init(self) -> Result<(), SetLoggerError> {
+        log::set_boxed_logger(Box::new(self))?;
+        log::set_max_level(self.level);
+        Ok(())
+    }
+
+    /// Send the record to the given writer, if it is enabled.
+    fn send(&self, writer: &UciWriter, record: &Record) {
+        if !self.enabled(record.metadata()) || (self.only_in_debug_mode && !writer.is_debug()) {
+            return;
+        }
+
+        // A line break would end the info command, the rest would be parsed as unknown command
+        let message = record.args().to_string();
+        let message = message.lines().collect::<Vec<_>>().join(" ");
+
+        // Logging must never fail the engine
+        writer
+            .send_string(format!("{} {message}", record.level()))
+            .ok();
+    }
+}
+
+impl Default for UciLogger {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Log for UciLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level
+    }
+
+    fn log(&self, record: &Record) {
+        // Don't hold the lock while writing
+        let writer = WRITER
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
+
+        if let Some(writer) = writer {
+            self.send(&writer, record);
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::test_utils::SharedBuffer;
+    use log::Level;
+
+    fn log_all(logger: UciLogger, writer: &UciWriter) {
+        for (level, message) in [
+            (Level::Error, "hash table\nnot allocated"),
+            (Level::Info, "loaded network\r\n"),
+            (Level::Debug, "aspiration window widened"),
+        ] {
+            logger.send(
+                writer,
+                &Record::builder()
+                    .level(level)
+                    .args(format_args!("{message}"))
+                    .build(),
+            );
+        }
+    }
+
+    #[test]
+    fn send_records_up_to_level() {
+        let output = SharedBuffer::default();
+        let writer = UciWriter::new(output.clone());
+
+        log_all(UciLogger::new(), &writer);
+
+        assert_eq!(
+            output.lines(),
+            [
+                "info string ERROR hash table not allocated",
+                "info string INFO loaded network",
+            ]
+        );
+    }
+
+    #[test]
+    fn send_records_only_in_debug_mode() {
+        let output = SharedBuffer::default();
+        let writer = UciWriter::new(output.clone());
+        let logger = UciLogger::new()
+            .with_level(LevelFilter::Debug)
+            .only_in_debug_mode();
+
+        log_all(logger, &writer);
+        writer.set_debug(true);
+        log_all(logger, &writer);
+
+        assert_eq!(
+            output.lines(),
+            [
+                "info string ERROR hash table not allocated",
+                "info string INFO loaded network",
+                "info string DEBUG aspiration window widened",
+            ]
+        );
+    }
+}
@@ -0,0 +1,83 @@
+//! A framework to implement UCI engines.
+//!
+//! Implement [`UciEngine`] for your engine and pass it to [`run`],
+//! which handles the communication with the GUI:
+//!
+//! ```
+//! use universal_chess_interface::{
+//!     command::{
+//!         engine_to_gui::{BestMoveCommand, InfoCommand},
+//!         gui_to_engine::{GoCommand, PositionCommand},
+//!     },
+//!     engine::{run, SearchContext, UciEngine},
+//! };
+//!
+//! struct MyEngine;
+//!
+//! impl UciEngine for MyEngine {
+//!     fn name(&self) -> String {
+//!         "My Engine".to_string()
+//!     }
+//!
+//!     fn author(&self) -> String {
+//!         "Me".to_string()
+//!     }
+//!
+//!     fn position(&mut self, _position: &PositionCommand) {}
+//!
+//!     fn go(&mut self, _go: &GoCommand, search: &SearchContext) -> BestMoveCommand {
+//!         search.send_info(InfoCommand::new().with_depth_plies(1)).ok();
+//!         BestMoveCommand::new("e2e4".parse().unwrap())
+//!     }
+//! }
+//!
+//! let input = "uci\nisready\nposition startpos\ngo depth 1\nquit\n";
+//! run(&mut MyEngine, input.as_bytes(), std::io::sink(), None).unwrap();
+//!
+//! // In a real engine:
+//! // run(&mut MyEngine, std::io::stdin().lock(), std::io::stdout(), None).unwrap();
+//! ```
+
+mod custom;
+mod debug;
+mod limits;
+#[cfg(feature = "log")]
+mod logger;
+mod poller;
+mod ponder;
+mod registry;
+mod run;
+mod search;
+mod session;
+mod startup;
+mod threaded;
+mod throttle;
+mod time;
+mod uci_engine;
+mod uci_options;
+mod writer;
+
+#[cfg(test)]
+pub(crate) mod test_utils;
+
+pub use self::custom::{bench, CustomCommand, Input, BENCH_POSITIONS, DEFAULT_BENCH_DEPTH};
+pub use self::limits::SearchLimits;
+#[cfg(feature = "log")]
+pub use self::logger::UciLogger;
+pub use self::poller::CommandPoller;
+pub use self::ponder::{IllegalTransition, Ponder, PonderEvent, PonderState};
+pub use self::registry::{OptionError, OptionRegistry, OptionValue};
+pub use self::run::{run, run_polled};
+pub use self::search::SearchContext;
+pub use self::startup::{configure, help, Startup, OPTION_VAR_PREFIX};
+pub use self::threaded::run_threaded;
+pub use self::throttle::{InfoThrottler, DEFAULT_INFO_INTERVAL, DEFAULT_INFO_WARM_UP};
+pub use self::time::{
+    Clock, ManualClock, SystemClock, TimeBudget, TimeManager, DEFAULT_MOVE_OVERHEAD,
+};
+pub use self::uci_engine::UciEngine;
+pub use self::uci_options::{ComboValue, OptionField, UciOptions};
+pub use self::writer::UciWriter;
+
+#[cfg(feature = "derive")]
+pub use universal_chess_interface_derive::{ComboValue, UciOptions};
@@ -0,0 +1,259 @@
+use std::{
+    collections::VecDeque,
+    io::{self, BufRead},
+    sync::mpsc::{self, Receiver, TryRecvError},
+    thread,
+};
+
+use crate::command::{engine_to_gui::EngineToGuiCommand, gui_to_engine::GuiToEngineCommand};
+
+use super::{session::parse_line, CustomCommand, Input, UciWriter};
+use crate::uci_debug;
+
+/// Checks for commands from the GUI without blocking, for engines that search on a single thread.
+///
+/// The input is read by a separate reader thread and passed to the poller via a channel,
+/// so the search only has to call [`CommandPoller::poll`] regularly, e.g. every few thousand nodes.
+/// While polling, `isready` is answered immediately and all other commands are queued
+/// until the search has finished.
+///
+/// The reader thread is blocked on the input most of the time.
+/// It ends when the input ends, but it is not joined when the poller is dropped.
+pub struct CommandPoller {
+    /// The lines read by the reader thread.
+    lines: Receiver<io::Result<String>>,
+
+    /// The writer to answer `isready`.
+    writer: UciWriter,
+
+    /// The commands that have been received during the search, but not handled yet.
+    queue: VecDeque<Input>,
+
+    /// Set when the search should stop.
+    stop: bool,
+
+    /// Set when `ponderhit` has been received during the search.
+    ponder_hit: bool,
+
+    /// Set when the input has ended.
+    is_closed: bool,
+}
+
+impl CommandPoller {
+    /// Create a new poller reading the commands from the input on a separate thread.
+    ///
+    /// `isready` is answered with the given writer.
+    pub fn new<R>(input: R, writer: UciWriter) -> Self
+    where
+        R: BufRead + Send + 'static,
+    {
+        let (sender, receiver) = mpsc::channel();
+
+        thread::spawn(move || {
+            for line in input.lines() {
+                let is_err = line.is_err();
+
+                if sender.send(line).is_err() || is_err {
+                    break;
+                }
+            }
+        });
+
+        Self {
+            lines: receiver,
+            writer,
+            queue: VecDeque::new(),
+            stop: false,
+            ponder_hit: false,
+            is_closed: false,
+        }
+    }
+
+    /// Handle the commands received since the last poll and determine if the search should stop.
+    ///
+    /// `isready` is answered immediately and `ponderhit` is remembered,
+    /// see [`CommandPoller::is_ponder_hit`].
+    /// `debug` turns the debug mode on or off immediately and is queued without stopping the search,
+    /// so that it can be passed to the engine after the search.
+    /// Any other command stops the search and is queued, to be returned by
+    /// [`CommandPoller::next_command`] after the search.
+    /// Unknown commands are ignored, without passing them to [`UciEngine::custom_command`](super::UciEngine::custom_command).
+    /// The search also has to stop when the input has ended.
+    pub fn poll(&mut self) -> io::Result<bool> {
+        if self.is_closed {
+            return Ok(true);
+        }
+
+        loop {
+            let line = match self.lines.try_recv() {
+                Ok(line) => line?,
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    self.is_closed = true;
+                    self.stop = true;
+                    break;
+                }
+            };
+
+            self.handle_line(&line)?;
+        }
+
+        Ok(self.stop)
+    }
+
+    /// Wait for the next command and determine if the search should stop.
+    ///
+    /// Like [`CommandPoller::poll`], but blocks until a line has been received,
+    /// e.g. while the best move of a finished search is held back until `stop` or `ponderhit`.
+    pub(crate) fn wait(&mut self) -> io::Result<bool> {
+        if !self.is_closed {
+            match self.lines.recv() {
+                Ok(line) => self.handle_line(&line?)?,
+                Err(_) => {
+                    self.is_closed = true;
+                    self.stop = true;
+                }
+            }
+        }
+
+        self.poll()
+    }
+
+    /// Determines if `ponderhit` has been received during the search.
+    pub fn is_ponder_hit(&self) -> bool {
+        self.ponder_hit
+    }
+
+    /// Reset the state of the poller for a new search.
+    pub fn start_search(&mut self) {
+        self.stop = false;
+        self.ponder_hit = false;
+    }
+
+    /// Handle a line received during the search.
+    fn handle_line(&mut self, line: &str) -> io::Result<()> {
+        let Some(command) = parse_line(&self.writer, line) else {
+            return Ok(());
+        };
+
+        match command {
+            Input::Uci(GuiToEngineCommand::IsReady) => {
+                self.writer.send(&EngineToGuiCommand::ReadyOk)?
+            }
+            Input::Uci(GuiToEngineCommand::Stop) => {
+                uci_debug!(self.writer, "stopping search");
+                self.stop = true;
+            }
+            Input::Uci(GuiToEngineCommand::PonderHit) => self.ponder_hit = true,
+            Input::Uci(GuiToEngineCommand::Debug(debug)) => {
+                self.writer.set_debug(debug);
+                self.queue.push_back(command);
+            }
+            Input::Custom(CustomCommand::Other(line)) => {
+                uci_debug!(self.writer, "ignored unknown command {line}");
+            }
+            command => {
+                uci_debug!(self.writer, "stopping search to handle {command}");
+                self.queue.push_back(command);
+                self.stop = true;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Wait for the next command.
+    ///
+    /// The commands queued during the search are returned first.
+    /// Returns `None` when the input has ended.
+    pub fn next_command(&mut self) -> io::Result<Option<Input>> {
+        if let Some(command) = self.queue.pop_front() {
+            return Ok(Some(command));
+        }
+
+        while !self.is_closed {
+            let Ok(line) = self.lines.recv() else {
+                self.is_closed = true;
+                break;
+            };
+
+            if let Some(command) = parse_line(&self.writer, &line?) {
+                return Ok(Some(command));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::engine::test_utils::SharedBuffer;
+
+    /// Poll until the input has been read completely.
+    fn poll_all(poller: &mut CommandPoller) -> bool {
+        while !poller.is_closed {
+            poller.poll().unwrap();
+            thread::sleep(Duration::from_millis(1));
+        }
+
+        poller.stop
+    }
+
+    #[test]
+    fn poll_answers_is_ready_and_queues_commands() {
+        let output = SharedBuffer::default();
+        let mut poller = CommandPoller::new(
+            "joho\nisready\nponderhit\nposition startpos\n".as_bytes(),
+            UciWriter::new(output.clone()),
+        );
+
+        assert!(poll_all(&mut poller));
+        assert!(poller.is_ponder_hit());
+        assert_eq!(output.lines(), ["readyok"]);
+
+        assert_eq!(
+            poller
+                .next_command()
+                .unwrap()
+                .map(|command| command.to_string()),
+            Some("position startpos".to_string())
+        );
+        assert_eq!(poller.next_command().unwrap(), None);
+    }
+
+    #[test]
+    fn next_command_skips_empty_lines() {
+        let mut poller = CommandPoller::new(
+            "\njoho\n  \nisready\nstop\n".as_bytes(),
+            UciWriter::new(io::sink()),
+        );
+
+        assert_eq!(
+            poller.next_command().unwrap(),
+            Some(Input::Custom(CustomCommand::Other("joho".to_string())))
+        );
+        assert_eq!(
+            poller.next_command().unwrap(),
+            Some(Input::Uci(GuiToEngineCommand::IsReady))
+        );
+        assert_eq!(
+            poller.next_command().unwrap(),
+            Some(Input::Uci(GuiToEngineCommand::Stop))
+        );
+        assert_eq!(poller.next_command().unwrap(), None);
+    }
+
+    #[test]
+    fn poll_stops_when_input_ends() {
+        let mut poller = CommandPoller::new("".as_bytes(), UciWriter::new(io::sink()));
+
+        assert!(poll_all(&mut poller));
+
+        poller.start_search();
+        assert!(poller.poll().unwrap());
+    }
+}
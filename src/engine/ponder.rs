@@ -0,0 +1,450 @@
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use crate::{
+    chess::Color,
+    command::{engine_to_gui::BestMoveCommand, gui_to_engine::GoCommand},
+};
+
+use super::{Clock, TimeManager};
+
+/// The state of a search with regard to pondering.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PonderState {
+    /// No search is running.
+    Idle,
+
+    /// A normal search is running, limited by the clock.
+    ///
+    /// After `go infinite`, the search may have finished already,
+    /// but no `bestmove` may be sent until `stop` is received.
+    Searching,
+
+    /// The engine is pondering on the opponent's move.
+    ///
+    /// No `bestmove` may be sent until `ponderhit` or `stop` is received.
+    Pondering,
+
+    /// The engine has finished pondering, but the GUI hasn't sent `ponderhit` or `stop` yet.
+    PonderFinished,
+
+    /// The GUI has sent `stop`, the engine has to send `bestmove` as soon as possible.
+    Stopping,
+}
+
+/// An event that changes the [`PonderState`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PonderEvent {
+    /// The GUI has sent `go`.
+    Go,
+
+    /// The GUI has sent `ponderhit`.
+    PonderHit,
+
+    /// The GUI has sent `stop`.
+    Stop,
+
+    /// The engine has finished the search.
+    Finish,
+}
+
+/// The event is not allowed in the current state, e.g. `ponderhit` during a normal search.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IllegalTransition {
+    /// The state in which the event occurred.
+    pub state: PonderState,
+
+    /// The event that is not allowed.
+    pub event: PonderEvent,
+}
+
+/// Keeps track of pondering, to send `bestmove` at the right time.
+///
+/// After `go ponder`, the engine must not send `bestmove` until `ponderhit` or `stop`
+/// is received, even if the search has finished.
+/// After `go infinite`, it must not send `bestmove` until `stop` is received.
+/// Such a best move is held back and returned when the GUI allows to send it.
+/// On `ponderhit`, the time manager is restarted with the clock of the `go ponder` command.
+///
+/// The best move can suggest the next ponder move with [`BestMoveCommand::new_with_ponder`].
+#[derive(Debug, Clone)]
+pub struct Ponder {
+    /// The current state.
+    state: PonderState,
+
+    /// The `go` command of the current search.
+    go: GoCommand,
+
+    /// The best move that is held back until `ponderhit` or `stop`.
+    best_move: Option<BestMoveCommand>,
+}
+
+impl Ponder {
+    /// Create a new state machine without a running search.
+    pub fn new() -> Self {
+        Self {
+            state: PonderState::Idle,
+            go: GoCommand::new(),
+            best_move: None,
+        }
+    }
+
+    /// The current state.
+    pub fn state(&self) -> PonderState {
+        self.state
+    }
+
+    /// Determines if the engine is pondering, i.e. the time is not limited yet.
+    pub fn is_pondering(&self) -> bool {
+        matches!(
+            self.state,
+            PonderState::Pondering | PonderState::PonderFinished
+        )
+    }
+
+    /// Determines if the search has finished, but its best move is held back.
+    pub fn is_best_move_held(&self) -> bool {
+        self.best_move.is_some()
+    }
+
+    /// Check that the event is allowed and change to the new state.
+    fn transition(
+        &mut self,
+        event: PonderEvent,
+        allowed: &[PonderState],
+        new_state: PonderState,
+    ) -> Result<(), IllegalTransition> {
+        if !allowed.contains(&self.state) {
+            return Err(IllegalTransition {
+                state: self.state,
+                event,
+            });
+        }
+
+        self.state = new_state;
+        Ok(())
+    }
+
+    /// A search has been started with `go`.
+    ///
+    /// The time manager is started, unless the engine is pondering.
+    pub fn go<C>(
+        &mut self,
+        go: &GoCommand,
+        time: &mut TimeManager<C>,
+        side_to_move: Color,
+    ) -> Result<(), IllegalTransition>
+    where
+        C: Clock,
+    {
+        let new_state = if go.ponder {
+            PonderState::Pondering
+        } else {
+            PonderState::Searching
+        };
+
+        self.transition(PonderEvent::Go, &[PonderState::Idle], new_state)?;
+        self.go = go.clone();
+
+        if !go.ponder {
+            time.start(go, side_to_move);
+        }
+
+        Ok(())
+    }
+
+    /// The GUI has sent `ponderhit`, the opponent has played the expected move.
+    ///
+    /// The search continues as a normal search and the time manager is started
+    /// with the clock of the `go ponder` command.
+    /// Returns the best move to send, if the search has already finished
+    /// and doesn't have to wait for `stop` because of `go infinite`.
+    pub fn ponder_hit<C>(
+        &mut self,
+        time: &mut TimeManager<C>,
+        side_to_move: Color,
+    ) -> Result<Option<BestMoveCommand>, IllegalTransition>
+    where
+        C: Clock,
+    {
+        self.transition(
+            PonderEvent::PonderHit,
+            &[PonderState::Pondering, PonderState::PonderFinished],
+            PonderState::Searching,
+        )?;
+
+        self.go.ponder = false;
+        time.start(&self.go, side_to_move);
+
+        if self.go.infinite {
+            return Ok(None);
+        }
+
+        Ok(self.take_best_move())
+    }
+
+    /// The GUI has sent `stop`.
+    ///
+    /// Returns the best move to send, if the search has already finished.
+    /// Otherwise, the engine must still send `bestmove` when it has stopped searching.
+    pub fn stop(&mut self) -> Result<Option<BestMoveCommand>, IllegalTransition> {
+        self.transition(
+            PonderEvent::Stop,
+            &[
+                PonderState::Searching,
+                PonderState::Pondering,
+                PonderState::PonderFinished,
+            ],
+            PonderState::Stopping,
+        )?;
+
+        Ok(self.take_best_move())
+    }
+
+    /// The engine has finished the search with the given best move.
+    ///
+    /// Returns the best move if it may be sent now,
+    /// or `None` if it's held back until `ponderhit` or `stop`.
+    pub fn finish(
+        &mut self,
+        best_move: BestMoveCommand,
+    ) -> Result<Option<BestMoveCommand>, IllegalTransition> {
+        if self.state == PonderState::Pondering {
+            self.state = PonderState::PonderFinished;
+            self.best_move = Some(best_move);
+            return Ok(None);
+        }
+
+        if self.state == PonderState::Searching && self.go.infinite {
+            self.best_move = Some(best_move);
+            return Ok(None);
+        }
+
+        self.transition(
+            PonderEvent::Finish,
+            &[PonderState::Searching, PonderState::Stopping],
+            PonderState::Idle,
+        )?;
+
+        Ok(Some(best_move))
+    }
+
+    /// Return the held back best move and end the search, if it has already finished.
+    fn take_best_move(&mut self) -> Option<BestMoveCommand> {
+        let best_move = self.best_move.take();
+
+        if best_move.is_some() {
+            self.state = PonderState::Idle;
+        }
+
+        best_move
+    }
+}
+
+impl Default for Ponder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The [`Ponder`] state shared by an engine loop and its searches,
+/// which may finish on another thread.
+///
+/// The engine manages its own time, so the time manager required by [`Ponder`] is not used here.
+#[derive(Clone, Default)]
+pub(crate) struct SharedPonder(Arc<Mutex<Ponder>>);
+
+impl SharedPonder {
+    /// Lock the state, even if a search has panicked while holding the lock.
+    fn lock(&self) -> MutexGuard<'_, Ponder> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// A search has been started with `go`.
+    ///
+    /// The best move still held back by the previous search is returned, it has to be sent first.
+    pub fn go(&self, go: &GoCommand) -> Option<BestMoveCommand> {
+        let mut ponder = self.lock();
+        let held = ponder.stop().ok().flatten();
+
+        *ponder = Ponder::new();
+        ponder.go(go, &mut TimeManager::new(), Color::White).ok();
+        held
+    }
+
+    /// The search has finished, see [`Ponder::finish`].
+    ///
+    /// The best move is also returned if the search hasn't been started with [`SharedPonder::go`].
+    pub fn finish(&self, best_move: BestMoveCommand) -> Option<BestMoveCommand> {
+        self.lock().finish(best_move).unwrap_or(Some(best_move))
+    }
+
+    /// The search has ended without a best move.
+    pub fn reset(&self) {
+        *self.lock() = Ponder::new();
+    }
+
+    /// The GUI has sent `ponderhit`, see [`Ponder::ponder_hit`].
+    pub fn ponder_hit(&self) -> Result<Option<BestMoveCommand>, IllegalTransition> {
+        self.lock()
+            .ponder_hit(&mut TimeManager::new(), Color::White)
+    }
+
+    /// The GUI has sent `stop`, see [`Ponder::stop`].
+    pub fn stop(&self) -> Result<Option<BestMoveCommand>, IllegalTransition> {
+        self.lock().stop()
+    }
+
+    /// Determines if the search has finished, but its best move is held back.
+    pub fn is_best_move_held(&self) -> bool {
+        self.lock().is_best_move_held()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{
+        chess::Move,
+        engine::{ManualClock, TimeBudget},
+    };
+
+    fn best_move() -> BestMoveCommand {
+        BestMoveCommand::new_with_ponder(
+            "e2e4".parse::<Move>().unwrap(),
+            "e7e5".parse::<Move>().unwrap(),
+        )
+    }
+
+    fn time_manager() -> (ManualClock, TimeManager<ManualClock>) {
+        let clock = ManualClock::new();
+        let time = TimeManager::with_clock(clock.clone()).with_move_overhead(Duration::ZERO);
+        (clock, time)
+    }
+
+    #[test]
+    fn normal_search_sends_best_move_when_finished() {
+        let (_, mut time) = time_manager();
+        let mut ponder = Ponder::new();
+
+        ponder
+            .go(&"go movetime 100".parse().unwrap(), &mut time, Color::White)
+            .unwrap();
+        assert_eq!(ponder.state(), PonderState::Searching);
+        assert!(time.budget().is_some());
+
+        assert_eq!(ponder.finish(best_move()), Ok(Some(best_move())));
+        assert_eq!(ponder.state(), PonderState::Idle);
+    }
+
+    #[test]
+    fn ponder_hit_restarts_time_manager() {
+        let (clock, mut time) = time_manager();
+        let mut ponder = Ponder::new();
+
+        let go = "go ponder wtime 1000 movestogo 1".parse().unwrap();
+        ponder.go(&go, &mut time, Color::White).unwrap();
+        assert!(ponder.is_pondering());
+        assert_eq!(time.budget(), None);
+
+        clock.advance(Duration::from_secs(5));
+        assert_eq!(ponder.ponder_hit(&mut time, Color::White), Ok(None));
+
+        assert_eq!(ponder.state(), PonderState::Searching);
+        assert_eq!(time.elapsed(), Duration::ZERO);
+        assert_eq!(
+            time.budget(),
+            Some(TimeBudget {
+                soft: Duration::from_millis(1000),
+                hard: Duration::from_millis(1000)
+            })
+        );
+
+        assert_eq!(ponder.finish(best_move()), Ok(Some(best_move())));
+    }
+
+    #[test]
+    fn finished_ponder_search_waits_for_ponder_hit() {
+        let (_, mut time) = time_manager();
+        let mut ponder = Ponder::new();
+
+        ponder
+            .go(&"go ponder".parse().unwrap(), &mut time, Color::Black)
+            .unwrap();
+        assert_eq!(ponder.finish(best_move()), Ok(None));
+        assert_eq!(ponder.state(), PonderState::PonderFinished);
+
+        assert_eq!(
+            ponder.ponder_hit(&mut time, Color::Black),
+            Ok(Some(best_move()))
+        );
+        assert_eq!(ponder.state(), PonderState::Idle);
+    }
+
+    #[test]
+    fn stop_while_pondering_still_sends_best_move() {
+        let (_, mut time) = time_manager();
+        let mut ponder = Ponder::new();
+
+        ponder
+            .go(&"go ponder".parse().unwrap(), &mut time, Color::White)
+            .unwrap();
+        assert_eq!(ponder.stop(), Ok(None));
+        assert_eq!(ponder.state(), PonderState::Stopping);
+
+        assert_eq!(ponder.finish(best_move()), Ok(Some(best_move())));
+        assert_eq!(ponder.state(), PonderState::Idle);
+    }
+
+    #[test]
+    fn finished_infinite_search_waits_for_stop() {
+        let (_, mut time) = time_manager();
+        let mut ponder = Ponder::new();
+
+        ponder
+            .go(
+                &"go ponder infinite".parse().unwrap(),
+                &mut time,
+                Color::White,
+            )
+            .unwrap();
+        assert_eq!(ponder.finish(best_move()), Ok(None));
+        assert_eq!(ponder.ponder_hit(&mut time, Color::White), Ok(None));
+        assert!(ponder.is_best_move_held());
+
+        assert_eq!(ponder.stop(), Ok(Some(best_move())));
+        assert_eq!(ponder.state(), PonderState::Idle);
+    }
+
+    #[test]
+    fn reject_illegal_transitions() {
+        let (_, mut time) = time_manager();
+        let mut ponder = Ponder::new();
+
+        assert_eq!(
+            ponder.stop(),
+            Err(IllegalTransition {
+                state: PonderState::Idle,
+                event: PonderEvent::Stop
+            })
+        );
+        assert!(ponder.finish(best_move()).is_err());
+        assert!(ponder.ponder_hit(&mut time, Color::White).is_err());
+
+        ponder
+            .go(&"go depth 3".parse().unwrap(), &mut time, Color::White)
+            .unwrap();
+        assert_eq!(
+            ponder.ponder_hit(&mut time, Color::White),
+            Err(IllegalTransition {
+                state: PonderState::Searching,
+                event: PonderEvent::PonderHit
+            })
+        );
+        assert!(ponder
+            .go(&"go depth 3".parse().unwrap(), &mut time, Color::White)
+            .is_err());
+        assert_eq!(ponder.state(), PonderState::Searching);
+    }
+}
@@ -0,0 +1,405 @@
+use std::fmt::Display;
+
+use crate::command::engine_to_gui::{EngineOption, OptionType, EMPTY_STRING};
+
+/// The current value of an engine option.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum OptionValue {
+    /// The value of a checkbox.
+    Check(bool),
+
+    /// The value of a spin wheel.
+    Spin(i64),
+
+    /// The selected value of a combo box, spelled as declared.
+    Combo(String),
+
+    /// A button, which doesn't have a value.
+    Button,
+
+    /// The value of a text field.
+    String(String),
+}
+
+impl OptionValue {
+    /// The default value of an option of the given type.
+    pub fn default_for(r#type: &OptionType) -> Self {
+        match r#type {
+            OptionType::Check(check) => OptionValue::Check(check.default),
+            OptionType::Spin(spin) => OptionValue::Spin(spin.default),
+            OptionType::Combo(combo) => OptionValue::Combo(combo.default.clone()),
+            OptionType::Button => OptionValue::Button,
+            OptionType::String(string) => OptionValue::String(string.default.clone()),
+        }
+    }
+
+    /// Parse and validate a value sent by the GUI for an option of the given type.
+    ///
+    /// Checkboxes accept `true` and `false`, spin wheels an integer in their range and
+    /// combo boxes one of their values, all case-insensitively.
+    /// Buttons don't accept a value and `<empty>` sets a text field to the empty string.
+    /// Returns `None` if the value is not valid.
+    pub fn parse(r#type: &OptionType, value: Option<&str>) -> Option<Self> {
+        let value = value.map(str::trim);
+
+        let value = match (r#type, value) {
+            (OptionType::Check(_), Some(value)) if value.eq_ignore_ascii_case("true") => {
+                OptionValue::Check(true)
+            }
+            (OptionType::Check(_), Some(value)) if value.eq_ignore_ascii_case("false") => {
+                OptionValue::Check(false)
+            }
+            (OptionType::Spin(spin), Some(value)) => {
+                let value = value.parse::<i64>().ok()?;
+
+                if value < spin.min || value > spin.max {
+                    return None;
+                }
+
+                OptionValue::Spin(value)
+            }
+            (OptionType::Combo(combo), Some(value)) => OptionValue::Combo(
+                combo
+                    .values
+                    .iter()
+                    .find(|var| var.eq_ignore_ascii_case(value))?
+                    .clone(),
+            ),
+            (OptionType::Button, None) => OptionValue::Button,
+            (OptionType::String(_), Some(EMPTY_STRING) | None) => {
+                OptionValue::String(String::new())
+            }
+            (OptionType::String(_), Some(value)) => OptionValue::String(value.to_string()),
+            _ => return None,
+        };
+
+        Some(value)
+    }
+}
+
+impl Display for OptionValue {
+    /// Format the value like it's sent in `setoption`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OptionValue::Check(value) => write!(f, "{value}"),
+            OptionValue::Spin(value) => write!(f, "{value}"),
+            OptionValue::Combo(value) | OptionValue::String(value) => write!(f, "{value}"),
+            OptionValue::Button => Ok(()),
+        }
+    }
+}
+
+/// The GUI tried to set an option to an invalid value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OptionError {
+    /// There is no option with the given name.
+    UnknownName(String),
+
+    /// The value is not valid for the option.
+    InvalidValue {
+        /// The name of the option.
+        name: String,
+
+        /// The rejected value.
+        value: Option<String>,
+    },
+}
+
+impl Display for OptionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OptionError::UnknownName(name) => write!(f, "unknown option {name}"),
+            OptionError::InvalidValue { name, value: None } => {
+                write!(f, "missing value for option {name}")
+            }
+            OptionError::InvalidValue {
+                name,
+                value: Some(value),
+            } => write!(f, "invalid value {value} for option {name}"),
+        }
+    }
+}
+
+/// A callback that is called when the value of an option has changed.
+type ChangeCallback = Box<dyn FnMut(&OptionValue) + Send>;
+
+/// An option declared in the registry.
+struct RegisteredOption {
+    /// The description of the option, sent on `uci`.
+    option: EngineOption,
+
+    /// The current value of the option.
+    value: OptionValue,
+
+    /// The callbacks to call when the value has changed.
+    callbacks: Vec<ChangeCallback>,
+}
+
+/// The options of an engine, together with their current values.
+///
+/// The options are declared once and then sent to the GUI on `uci`.
+/// Values sent with `setoption` are validated before they are applied,
+/// invalid values are reported to the GUI as `info string`.
+/// See [`UciEngine::option_registry`](super::UciEngine::option_registry).
+///
+/// Like in the UCI protocol, the names of the options are not case-sensitive.
+#[derive(Default)]
+pub struct OptionRegistry {
+    /// The declared options, in the order they are sent to the GUI.
+    options: Vec<RegisteredOption>,
+}
+
+impl OptionRegistry {
+    /// Create a new registry without any options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declare a new option, initialized to its default value.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if an option with the same name has already been declared.
+    pub fn with_option(mut self, option: EngineOption) -> Self {
+        assert!(
+            self.find(&option.name).is_none(),
+            "The option {} has already been declared",
+            option.name
+        );
+
+        self.options.push(RegisteredOption {
+            value: OptionValue::default_for(&option.r#type),
+            option,
+            callbacks: Vec::new(),
+        });
+        self
+    }
+
+    /// Call the callback whenever the option with the given name is set, or its button is pressed.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if there is no option with the given name.
+    pub fn on_change<F>(&mut self, name: &str, callback: F)
+    where
+        F: FnMut(&OptionValue) + Send + 'static,
+    {
+        let Some(index) = self.find(name) else {
+            panic!("The option {name} has not been declared");
+        };
+
+        self.options[index].callbacks.push(Box::new(callback));
+    }
+
+    /// The index of the option with the given name.
+    fn find(&self, name: &str) -> Option<usize> {
+        self.options
+            .iter()
+            .position(|registered| registered.option.name.eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// Determines if an option with the given name has been declared.
+    pub fn contains(&self, name: &str) -> bool {
+        self.find(name).is_some()
+    }
+
+    /// The descriptions of all options, to send them to the GUI.
+    pub fn options(&self) -> Vec<EngineOption> {
+        self.options
+            .iter()
+            .map(|registered| registered.option.clone())
+            .collect()
+    }
+
+    /// Validate the value sent by the GUI and apply it to the option.
+    ///
+    /// Returns the declared name of the option and its new value.
+    /// The value is `None` for buttons.
+    pub fn set(
+        &mut self,
+        name: &str,
+        value: Option<&str>,
+    ) -> Result<(&str, &OptionValue), OptionError> {
+        let index = self
+            .find(name)
+            .ok_or_else(|| OptionError::UnknownName(name.to_string()))?;
+        let registered = &mut self.options[index];
+
+        let new_value = OptionValue::parse(&registered.option.r#type, value).ok_or_else(|| {
+            OptionError::InvalidValue {
+                name: registered.option.name.clone(),
+                value: value.map(str::to_string),
+            }
+        })?;
+
+        registered.value = new_value;
+
+        for callback in &mut registered.callbacks {
+            callback(&registered.value);
+        }
+
+        Ok((&registered.option.name, &registered.value))
+    }
+
+    /// The current value of the option with the given name.
+    pub fn value(&self, name: &str) -> Option<&OptionValue> {
+        self.find(name).map(|index| &self.options[index].value)
+    }
+
+    /// The current value of the checkbox with the given name.
+    pub fn check(&self, name: &str) -> Option<bool> {
+        match self.value(name)? {
+            OptionValue::Check(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// The current value of the spin wheel with the given name.
+    pub fn spin(&self, name: &str) -> Option<i64> {
+        match self.value(name)? {
+            OptionValue::Spin(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// The current value of the combo box with the given name.
+    pub fn combo(&self, name: &str) -> Option<&str> {
+        match self.value(name)? {
+            OptionValue::Combo(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// The current value of the text field with the given name.
+    pub fn string(&self, name: &str) -> Option<&str> {
+        match self.value(name)? {
+            OptionValue::String(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::command::engine_to_gui::{CheckOption, ComboOption, StringOption};
+    use rstest::rstest;
+
+    fn registry() -> OptionRegistry {
+        OptionRegistry::new()
+            .with_option(EngineOption::hash(1, 1024, 16))
+            .with_option(EngineOption::new(
+                "Ponder",
+                OptionType::Check(CheckOption { default: false }),
+            ))
+            .with_option(EngineOption::new(
+                "Style",
+                OptionType::Combo(ComboOption::new(["Solid", "Normal", "Risky"], "Normal")),
+            ))
+            .with_option(EngineOption::new("Clear Hash", OptionType::Button))
+            .with_option(EngineOption::new(
+                "SyzygyPath",
+                OptionType::String(StringOption {
+                    default: "/tb".to_string(),
+                }),
+            ))
+    }
+
+    #[rstest]
+    #[case("Hash", Some("256"), OptionValue::Spin(256))]
+    #[case("hash", Some("1"), OptionValue::Spin(1))]
+    #[case("Ponder", Some("true"), OptionValue::Check(true))]
+    #[case("Ponder", Some("FALSE"), OptionValue::Check(false))]
+    #[case("Style", Some("risky"), OptionValue::Combo("Risky".to_string()))]
+    #[case("Clear Hash", None, OptionValue::Button)]
+    #[case("SyzygyPath", Some("/a b"), OptionValue::String("/a b".to_string()))]
+    #[case("SyzygyPath", Some("<empty>"), OptionValue::String(String::new()))]
+    fn set_option_ok(
+        #[case] name: &str,
+        #[case] value: Option<&str>,
+        #[case] expected: OptionValue,
+    ) {
+        let mut registry = registry();
+
+        assert_eq!(registry.set(name, value).unwrap().1, &expected);
+        assert_eq!(registry.value(name), Some(&expected));
+    }
+
+    #[rstest]
+    #[case("Hash", Some("0"))]
+    #[case("Hash", Some("1025"))]
+    #[case("Hash", Some("big"))]
+    #[case("Hash", None)]
+    #[case("Ponder", Some("yes"))]
+    #[case("Style", Some("Wild"))]
+    #[case("Clear Hash", Some("true"))]
+    fn set_option_invalid_value(#[case] name: &str, #[case] value: Option<&str>) {
+        let mut registry = registry();
+        let old_value = registry.value(name).cloned();
+
+        assert!(matches!(
+            registry.set(name, value),
+            Err(OptionError::InvalidValue { .. })
+        ));
+        assert_eq!(registry.value(name).cloned(), old_value);
+    }
+
+    #[test]
+    fn set_unknown_option() {
+        assert_eq!(
+            registry().set("Threads", Some("2")),
+            Err(OptionError::UnknownName("Threads".to_string()))
+        );
+    }
+
+    #[test]
+    fn typed_getters() {
+        let registry = registry();
+
+        assert_eq!(registry.spin("Hash"), Some(16));
+        assert_eq!(registry.check("Ponder"), Some(false));
+        assert_eq!(registry.combo("Style"), Some("Normal"));
+        assert_eq!(registry.string("SyzygyPath"), Some("/tb"));
+        assert_eq!(registry.spin("Ponder"), None);
+        assert_eq!(registry.spin("Threads"), None);
+    }
+
+    #[test]
+    fn call_change_callbacks() {
+        let mut registry = registry();
+        let changes = Arc::new(Mutex::new(Vec::new()));
+
+        let recorded = changes.clone();
+        registry.on_change("Clear Hash", move |value| {
+            recorded.lock().unwrap().push(value.clone())
+        });
+
+        registry.set("Clear Hash", None).unwrap();
+        registry.set("Hash", Some("32")).unwrap();
+        registry.set("Clear Hash", Some("now")).unwrap_err();
+
+        assert_eq!(*changes.lock().unwrap(), [OptionValue::Button]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn declare_option_twice() {
+        registry().with_option(EngineOption::hash(1, 2, 1));
+    }
+
+    #[rstest]
+    #[case(
+        OptionError::InvalidValue { name: "Hash".to_string(), value: Some("0".to_string()) },
+        "invalid value 0 for option Hash"
+    )]
+    #[case(
+        OptionError::InvalidValue { name: "Hash".to_string(), value: None },
+        "missing value for option Hash"
+    )]
+    #[case(OptionError::UnknownName("Threads".to_string()), "unknown option Threads")]
+    fn format_option_error(#[case] error: OptionError, #[case] expected: &str) {
+        assert_eq!(error.to_string(), expected);
+    }
+}
@@ -0,0 +1,443 @@
+use std::{
+    fs::File,
+    io::{self, BufRead, Write},
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use super::{
+    session::{Next, Session},
+    CommandPoller, UciEngine,
+};
+use crate::uci_debug;
+
+/// Run the engine, reading commands from the input and sending the answers to the output.
+///
+/// Usually, the input is stdin and the output is stdout.
+/// The loop answers `uci` and `isready` on its own, passes all other commands to the engine
+/// and returns when `quit` is received or the input ends.
+/// The communication is logged to the `debug_log_file` returned by [`configure`](super::configure),
+/// until the GUI sets another `Debug Log File`.
+///
+/// The search is run on the current thread, so the engine can't receive `stop` while searching.
+/// After `go ponder` and `go infinite`, the engine may return right away,
+/// the best move is held back until `stop` or `ponderhit` is received.
+/// Use [`run_threaded`](super::run_threaded) to search on a separate thread
+/// or [`run_polled`] to check for commands during the search instead.
+pub fn run<E, R, W>(
+    engine: &mut E,
+    input: R,
+    output: W,
+    debug_log_file: Option<File>,
+) -> io::Result<()>
+where
+    E: UciEngine,
+    R: BufRead,
+    W: Write + Send + 'static,
+{
+    let mut session = Session::new(output, debug_log_file);
+
+    for line in input.lines() {
+        let Some(command) = session.parse(&line?) else {
+            continue;
+        };
+
+        match session.handle(engine, command)? {
+            Next::Continue => {}
+            Next::Search(go) => {
+                uci_debug!(session.writer, "search started");
+
+                session.search_context(&go)?.run(engine, &go)?;
+            }
+            Next::Quit => break,
+        }
+    }
+
+    session.release_best_move()
+}
+
+/// Run the engine on the current thread, checking for commands during the search.
+///
+/// Like [`run`], but the input is read on a separate thread by a [`CommandPoller`].
+/// The engine has to call [`SearchContext::poll`](super::SearchContext::poll) regularly while searching:
+///
+/// - `isready` is answered immediately, without stopping the search.
+/// - `stop` stops the search and `ponderhit` is passed to the search via the [`SearchContext`](super::SearchContext).
+/// - `debug` turns the debug mode on or off immediately,
+///   but [`UciEngine::debug`] is only called after the search has finished.
+/// - Any other command stops the search and is handled after the search has finished.
+pub fn run_polled<E, R, W>(
+    engine: &mut E,
+    input: R,
+    output: W,
+    debug_log_file: Option<File>,
+) -> io::Result<()>
+where
+    E: UciEngine,
+    R: BufRead + Send + 'static,
+    W: Write + Send + 'static,
+{
+    let mut session = Session::new(output, debug_log_file);
+    let poller = Arc::new(Mutex::new(CommandPoller::new(
+        input,
+        session.writer.clone(),
+    )));
+    let lock = || poller.lock().unwrap_or_else(PoisonError::into_inner);
+
+    loop {
+        // Don't hold the lock during the search
+        let Some(command) = lock().next_command()? else {
+            break;
+        };
+
+        match session.handle(engine, command)? {
+            Next::Continue => {}
+            Next::Search(go) => {
+                uci_debug!(session.writer, "search started");
+                lock().start_search();
+
+                session
+                    .search_context(&go)?
+                    .with_poller(poller.clone())
+                    .run(engine, &go)?;
+            }
+            Next::Quit => break,
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chess::MoveLine;
+    use crate::command::engine_to_gui::{ComboOption, EngineOption, OptionType};
+    use crate::engine::{
+        test_utils::{run_engine, SharedBuffer, TestEngine},
+        OptionRegistry,
+    };
+    use rstest::rstest;
+
+    #[test]
+    fn run_uci_handshake() {
+        let (engine, output) = run_engine(TestEngine::default(), "uci\nisready\nquit\n");
+
+        assert_eq!(
+            output,
+            [
+                "id name Test Engine",
+                "id author Test Author",
+                "option name Hash type spin default 16 min 1 max 1024",
+                "option name Debug Log File type string default <empty>",
+                "uciok",
+                "readyok",
+            ]
+        );
+        assert!(engine.received.is_empty());
+    }
+
+    #[test]
+    fn run_search() {
+        let (engine, output) = run_engine(
+            TestEngine::default(),
+            "ucinewgame\nsetoption name Hash value 32\nposition startpos moves e2e4\ngo depth 1\n",
+        );
+
+        assert_eq!(output, ["info depth 1", "bestmove e7e5"]);
+        assert_eq!(
+            engine.received,
+            [
+                "ucinewgame",
+                "setoption name Hash value 32",
+                "position startpos moves e2e4",
+                "go depth 1",
+            ]
+        );
+    }
+
+    #[test]
+    fn run_ignores_unknown_commands_and_stops_on_quit() {
+        let (engine, output) = run_engine(
+            TestEngine::default(),
+            "\njoho\nhello isready\nquit\nisready\n",
+        );
+
+        assert_eq!(output, ["readyok"]);
+        assert!(engine.received.is_empty());
+    }
+
+    #[test]
+    fn run_validates_registered_options() {
+        let registry = OptionRegistry::new()
+            .with_option(EngineOption::new(
+                "Style",
+                OptionType::Combo(ComboOption::new(["Solid", "Risky"], "Solid")),
+            ))
+            .with_option(EngineOption::new("Clear Hash", OptionType::Button));
+        let engine = TestEngine {
+            registry: Some(registry),
+            ..Default::default()
+        };
+
+        let (engine, output) = run_engine(
+            engine,
+            "uci\nsetoption name style value risky\nsetoption name Style value Wild\n\
+            setoption name Clear Hash\nsetoption name Hash value 0\nsetoption name hash value 64\n\
+            setoption name Threads value 4\n",
+        );
+
+        assert_eq!(
+            output,
+            [
+                "id name Test Engine",
+                "id author Test Author",
+                "option name Hash type spin default 16 min 1 max 1024",
+                "option name Style type combo default Solid var Solid var Risky",
+                "option name Clear Hash type button",
+                "option name Debug Log File type string default <empty>",
+                "uciok",
+                "info string invalid value Wild for option Style",
+                "info string invalid value 0 for option Hash",
+            ]
+        );
+        assert_eq!(
+            engine.received,
+            [
+                "setoption name Style value Risky",
+                "setoption name Clear Hash",
+                "setoption name Hash value 64",
+                "setoption name Threads value 4",
+            ]
+        );
+        assert_eq!(engine.registry.unwrap().combo("Style"), Some("Risky"));
+    }
+
+    #[test]
+    fn run_echoes_commands_in_debug_mode() {
+        let (_, output) = run_engine(
+            TestEngine::default(),
+            "debug on\nisready\njoho\ngo depth 1\ndebug off\nisready\n",
+        );
+
+        assert_eq!(
+            output,
+            [
+                "info string debug mode on",
+                "info string received isready",
+                "readyok",
+                "info string received joho",
+                "info string ignored unknown command joho",
+                "info string received go depth 1",
+                "info string search started",
+                "info depth 1",
+                "bestmove e7e5",
+                "info string received debug off",
+                "readyok",
+            ]
+        );
+    }
+
+    #[test]
+    fn run_checks_copy_protection_and_registration() {
+        let engine = TestEngine {
+            copy_protection: Some(false),
+            registration_code: Some("1234".to_string()),
+            ..Default::default()
+        };
+
+        let (engine, output) = run_engine(
+            engine,
+            "uci\nregister later\nregister name Jane Doe code 0000\n\
+            register name Jane Doe code 1234\n",
+        );
+
+        assert_eq!(
+            output,
+            [
+                "id name Test Engine",
+                "id author Test Author",
+                "option name Hash type spin default 16 min 1 max 1024",
+                "option name Debug Log File type string default <empty>",
+                "uciok",
+                "copyprotection checking",
+                "copyprotection error",
+                "registration checking",
+                "registration error",
+                "registration checking",
+                "registration error",
+                "registration checking",
+                "registration ok",
+            ]
+        );
+        assert_eq!(
+            engine.received,
+            [
+                "register later",
+                "register name Jane Doe code 0000",
+                "register name Jane Doe code 1234",
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case("d2d4", None, "bestmove d2d4")]
+    #[case("", Some("g1f3"), "bestmove g1f3")]
+    #[case("", None, "bestmove 0000")]
+    fn run_sends_fallback_move_if_search_panics(
+        #[case] pv: MoveLine,
+        #[case] fallback_move: Option<&str>,
+        #[case] expected: &str,
+    ) {
+        let engine = TestEngine {
+            panic_after: Some(pv),
+            fallback_move: fallback_move.map(|mv| mv.parse().unwrap()),
+            ..Default::default()
+        };
+
+        let (_, output) = run_engine(engine, "go depth 1\nisready\n");
+
+        assert_eq!(
+            &output[output.len() - 3..],
+            [
+                "info string search panicked: search exploded",
+                expected,
+                "readyok"
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case::ponder_hit("go ponder\nisready\nponderhit\n", "ponderhit")]
+    #[case::stop("go infinite\nisready\nstop\n", "stop")]
+    #[case::quit("go ponder\nisready\nquit\n", "quit")]
+    fn run_holds_back_best_move_until_stop_or_ponder_hit(
+        #[case] input: &str,
+        #[case] last_command: &str,
+    ) {
+        let engine = TestEngine {
+            return_early: true,
+            ..Default::default()
+        };
+
+        let (engine, output) = run_engine(engine, input);
+
+        assert_eq!(output, ["info depth 1", "readyok", "bestmove e7e5"]);
+        assert!(!engine
+            .received
+            .iter()
+            .any(|command| command == last_command));
+    }
+
+    #[test]
+    fn run_logs_communication_to_debug_log_file() {
+        let path =
+            std::env::temp_dir().join(format!("uci-transcript-{}-run.log", std::process::id()));
+        std::fs::remove_file(&path).ok();
+
+        let input = format!(
+            "isready\nsetoption name Debug Log File value {}\nisready\ngo depth 1\n\
+            setoption name debug log file value <empty>\nisready\n",
+            path.display()
+        );
+        let (engine, output) = run_engine(TestEngine::default(), &input);
+
+        let transcript = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        // Skip the timestamps
+        let lines: Vec<_> = transcript
+            .lines()
+            .map(|line| line.split_once(' ').unwrap().1)
+            .collect();
+        assert_eq!(
+            lines,
+            [
+                "in  isready",
+                "out readyok",
+                "in  go depth 1",
+                "out info depth 1",
+                "out bestmove e7e5",
+                "in  setoption name debug log file value <empty>",
+            ]
+        );
+        assert_eq!(output.len(), 5);
+        assert_eq!(engine.received, ["go depth 1"]);
+    }
+
+    fn run_polled_engine(input: &'static str) -> (TestEngine, Vec<String>) {
+        let mut engine = TestEngine::default();
+        let output = SharedBuffer::default();
+        run_polled(&mut engine, input.as_bytes(), output.clone(), None).unwrap();
+
+        (engine, output.lines())
+    }
+
+    #[test]
+    fn run_polled_answers_is_ready_during_search() {
+        let (engine, output) = run_polled_engine("go infinite\nisready\nstop\nquit\n");
+
+        assert_eq!(output, ["readyok", "info depth 1", "bestmove e7e5"]);
+        assert_eq!(engine.received, ["go infinite"]);
+    }
+
+    #[test]
+    fn run_polled_switches_debug_mode_during_search() {
+        let (engine, output) = run_polled_engine("go infinite\ndebug on\nisready\nstop\n");
+
+        assert_eq!(
+            output,
+            [
+                "info string received isready",
+                "readyok",
+                "info string received stop",
+                "info string stopping search",
+                "info depth 1",
+                "bestmove e7e5",
+                "info string debug mode on",
+            ]
+        );
+        assert!(engine.debug);
+        assert_eq!(engine.received, ["go infinite"]);
+    }
+
+    #[rstest]
+    #[case::ponder_hit("go ponder\nisready\nponderhit\n")]
+    #[case::stop("go ponder infinite\nponderhit\nisready\nstop\n")]
+    fn run_polled_holds_back_best_move(#[case] input: &'static str) {
+        let mut engine = TestEngine {
+            return_early: true,
+            ..Default::default()
+        };
+        let output = SharedBuffer::default();
+        run_polled(&mut engine, input.as_bytes(), output.clone(), None).unwrap();
+
+        assert_eq!(output.lines(), ["info depth 1", "readyok", "bestmove e7e5"]);
+    }
+
+    #[test]
+    fn run_polled_handles_queued_commands_after_search() {
+        let (engine, output) =
+            run_polled_engine("go infinite\nposition startpos\ngo depth 1\nquit\n");
+
+        assert_eq!(
+            output,
+            [
+                "info depth 1",
+                "bestmove e7e5",
+                "info depth 1",
+                "bestmove e7e5"
+            ]
+        );
+        assert_eq!(
+            engine.received,
+            ["go infinite", "position startpos", "go depth 1"]
+        );
+    }
+
+    #[test]
+    fn run_polled_finishes_search_on_end_of_input() {
+        let (_, output) = run_polled_engine("go ponder\n");
+
+        assert_eq!(output, ["info depth 1", "bestmove e7e5"]);
+    }
+}
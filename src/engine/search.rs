@@ -0,0 +1,310 @@
+use std::{
+    any::Any,
+    io,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex, PoisonError,
+    },
+};
+
+use crate::{
+    chess::Move,
+    command::{
+        engine_to_gui::{BestMoveCommand, EngineToGuiCommand, InfoCommand},
+        gui_to_engine::GoCommand,
+    },
+};
+
+use super::{ponder::SharedPonder, CommandPoller, UciEngine, UciWriter};
+
+/// The context of a search started with `go`.
+///
+/// It allows the engine to send information to the GUI while searching
+/// and to find out if the search should be stopped.
+/// The context can be cloned to share it with helper threads.
+#[derive(Clone)]
+pub struct SearchContext {
+    /// The writer to send information to the GUI.
+    writer: UciWriter,
+
+    /// Set when the search should stop as soon as possible.
+    stop: Arc<AtomicBool>,
+
+    /// Set when the GUI has sent `ponderhit` during the search.
+    ponder_hit: Arc<AtomicBool>,
+
+    /// The poller to check for commands, if the search runs on the thread reading the commands.
+    poller: Option<Arc<Mutex<CommandPoller>>>,
+
+    /// Holds back the best move after `go ponder` and `go infinite`.
+    ponder: SharedPonder,
+
+    /// The first move of the best line sent so far.
+    best_move: Arc<Mutex<Option<Move>>>,
+
+    /// The number of nodes sent last, or `u64::MAX` if none has been sent.
+    node_count: Arc<AtomicU64>,
+}
+
+impl SearchContext {
+    /// Create a new search context sending to the given writer.
+    pub fn new(writer: UciWriter) -> Self {
+        Self {
+            writer,
+            stop: Arc::new(AtomicBool::new(false)),
+            ponder_hit: Arc::new(AtomicBool::new(false)),
+            poller: None,
+            ponder: SharedPonder::default(),
+            best_move: Arc::new(Mutex::new(None)),
+            node_count: Arc::new(AtomicU64::new(u64::MAX)),
+        }
+    }
+
+    /// Check for commands with the given poller.
+    pub(crate) fn with_poller(mut self, poller: Arc<Mutex<CommandPoller>>) -> Self {
+        self.poller = Some(poller);
+        self
+    }
+
+    /// Start the search in the pondering state shared with the engine loop,
+    /// which may receive `stop` or `ponderhit` after the search has finished.
+    ///
+    /// A best move still held back by the previous search is sent first.
+    pub(crate) fn with_ponder(mut self, ponder: SharedPonder, go: &GoCommand) -> io::Result<Self> {
+        if let Some(best_move) = ponder.go(go) {
+            self.send_best_move(best_move)?;
+        }
+
+        self.ponder = ponder;
+        Ok(self)
+    }
+
+    /// Send information about the search to the GUI.
+    ///
+    /// The first move of the best line is remembered,
+    /// to send it as `bestmove` if the search panics.
+    /// The number of nodes is remembered for `bench`.
+    pub fn send_info(&self, info: InfoCommand) -> io::Result<()> {
+        if let Some(node_count) = info.node_count() {
+            self.node_count.store(node_count, Ordering::Relaxed);
+        }
+
+        if let Some(mv) = info
+            .pv()
+            .filter(|(kth_best, _)| *kth_best == 1)
+            .and_then(|(_, pv)| pv.first())
+        {
+            *self
+                .best_move
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner) = Some(mv);
+        }
+
+        self.writer.send_info(info)
+    }
+
+    /// The number of nodes sent last.
+    pub fn node_count_so_far(&self) -> Option<u64> {
+        let node_count = self.node_count.load(Ordering::Relaxed);
+        (node_count != u64::MAX).then_some(node_count)
+    }
+
+    /// The first move of the best line sent so far.
+    pub fn best_move_so_far(&self) -> Option<Move> {
+        *self
+            .best_move
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Send any text to the GUI, as `info string`.
+    pub fn send_string<S>(&self, string: S) -> io::Result<()>
+    where
+        S: Into<String>,
+    {
+        self.writer.send_string(string)
+    }
+
+    /// Determines if the GUI has turned on the debug mode with `debug on`.
+    ///
+    /// Use [`uci_debug!`](crate::uci_debug) to send debug information only in debug mode.
+    pub fn is_debug(&self) -> bool {
+        self.writer.is_debug()
+    }
+
+    /// Determines if the search should stop as soon as possible.
+    ///
+    /// The engine should check this regularly and return the best move found so far.
+    pub fn should_stop(&self) -> bool {
+        self.stop.load(Ordering::Relaxed)
+    }
+
+    /// Check for new commands from the GUI and determine if the search should stop.
+    ///
+    /// Engines that search on the thread reading the commands, like with
+    /// [`run_polled`](super::run_polled), have to call this regularly, e.g. every few thousand nodes.
+    /// Otherwise, this is the same as [`SearchContext::should_stop`].
+    ///
+    /// If the answer to `isready` can't be sent, the search should stop as well.
+    pub fn poll(&self) -> bool {
+        if let Some(poller) = &self.poller {
+            let mut poller = poller.lock().unwrap_or_else(PoisonError::into_inner);
+
+            if poller.poll().unwrap_or(true) {
+                self.stop_search().ok();
+            }
+
+            if poller.is_ponder_hit() && !self.is_ponder_hit() {
+                self.set_ponder_hit().ok();
+            }
+        }
+
+        self.should_stop()
+    }
+
+    /// Request the search to stop as soon as possible.
+    pub fn stop(&self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+
+    /// Determines if the GUI has sent `ponderhit` during the search.
+    ///
+    /// From then on, the engine should continue the search in normal mode.
+    pub fn is_ponder_hit(&self) -> bool {
+        self.ponder_hit.load(Ordering::Relaxed)
+    }
+
+    /// Notify the search that the GUI has sent `ponderhit`.
+    ///
+    /// If the search has already finished, its best move is sent now.
+    pub(crate) fn set_ponder_hit(&self) -> io::Result<()> {
+        self.ponder_hit.store(true, Ordering::Relaxed);
+
+        match self.ponder.ponder_hit() {
+            Ok(Some(best_move)) => self.send_best_move(best_move),
+            _ => Ok(()),
+        }
+    }
+
+    /// Stop the search because the GUI has sent `stop`.
+    ///
+    /// If the search has already finished, its best move is sent now.
+    pub(crate) fn stop_search(&self) -> io::Result<()> {
+        self.stop();
+
+        match self.ponder.stop() {
+            Ok(Some(best_move)) => self.send_best_move(best_move),
+            _ => Ok(()),
+        }
+    }
+
+    /// Determines if the search has finished, but its best move is held back
+    /// until the GUI sends `stop` or `ponderhit`.
+    pub(crate) fn is_best_move_held(&self) -> bool {
+        self.ponder.is_best_move_held()
+    }
+
+    /// Run the search of the engine and send the best move to the GUI.
+    ///
+    /// If the search panics, the panic is reported as `info string` and a fallback move is sent:
+    /// the best move found so far, the engine's [`UciEngine::fallback_move`] or the null move.
+    /// Like this, the GUI doesn't wait for `bestmove` forever.
+    ///
+    /// After `go ponder` and `go infinite`, the best move is held back until the GUI sends
+    /// `stop` or `ponderhit`, even if the engine has finished the search earlier,
+    /// if the context has been created with [`SearchContext::with_ponder`].
+    /// With a poller, this waits for these commands, otherwise the engine loop has to pass them
+    /// to [`SearchContext::stop_search`] and [`SearchContext::set_ponder_hit`].
+    pub(crate) fn run<E>(&self, engine: &mut E, go: &GoCommand) -> io::Result<()>
+    where
+        E: UciEngine,
+    {
+        let best_move = match panic::catch_unwind(AssertUnwindSafe(|| engine.go(go, self))) {
+            Ok(best_move) => best_move,
+            Err(payload) => {
+                self.send_string(format!("search panicked: {}", panic_message(&*payload)))?;
+
+                let fallback_move = self.best_move_so_far().or_else(|| {
+                    panic::catch_unwind(AssertUnwindSafe(|| engine.fallback_move()))
+                        .ok()
+                        .flatten()
+                });
+
+                match fallback_move {
+                    Some(mv) => BestMoveCommand::new(mv),
+                    // The null move, which the GUI can't play, so it's not held back
+                    None => {
+                        self.ponder.reset();
+                        return self.writer.send_line("bestmove 0000");
+                    }
+                }
+            }
+        };
+
+        match self.ponder.finish(best_move) {
+            Some(best_move) => self.send_best_move(best_move),
+            None => self.wait_for_release(),
+        }
+    }
+
+    /// Wait until the GUI allows to send the held back best move, if there is a poller.
+    fn wait_for_release(&self) -> io::Result<()> {
+        let Some(poller) = &self.poller else {
+            return Ok(());
+        };
+
+        while self.is_best_move_held() {
+            let mut poller = poller.lock().unwrap_or_else(PoisonError::into_inner);
+
+            if poller.wait()? {
+                self.stop_search()?;
+            } else if poller.is_ponder_hit() && !self.is_ponder_hit() {
+                self.set_ponder_hit()?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Send the best move to the GUI.
+    fn send_best_move(&self, best_move: BestMoveCommand) -> io::Result<()> {
+        self.writer.send(&EngineToGuiCommand::BestMove(best_move))
+    }
+}
+
+/// The message of a panic, if it has one.
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message
+    } else {
+        "unknown error"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chess::MoveLine;
+
+    #[test]
+    fn remember_best_move_so_far() {
+        let context = SearchContext::new(UciWriter::new(io::sink()));
+        assert_eq!(context.best_move_so_far(), None);
+
+        let pv = "e2e4 e7e5".parse::<MoveLine>().unwrap();
+        let second_pv = "d2d4".parse::<MoveLine>().unwrap();
+
+        context.send_info(InfoCommand::new().with_pv(pv)).unwrap();
+        context
+            .send_info(InfoCommand::new().with_multi_pv(2, second_pv))
+            .unwrap();
+        context
+            .send_info(InfoCommand::new().with_node_count(10))
+            .unwrap();
+
+        assert_eq!(context.best_move_so_far(), "e2e4".parse().ok());
+    }
+}
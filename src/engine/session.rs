@@ -0,0 +1,333 @@
+use std::{
+    fs::File,
+    io::{self, Write},
+    path::Path,
+};
+
+use crate::{
+    chess::Variant,
+    command::{
+        engine_to_gui::{
+            CopyProtectionCommand, EngineOption, EngineToGuiCommand, IdCommand, InfoCommand,
+            RegistrationCommand,
+        },
+        gui_to_engine::{GoCommand, GuiToEngineCommand, RegisterCommand, SetOptionCommand},
+    },
+};
+
+use super::{
+    custom::{self, parse_input, CurrentPosition, Input},
+    ponder::SharedPonder,
+    writer::open_transcript,
+    OptionError, OptionValue, SearchContext, UciEngine, UciWriter,
+};
+use crate::uci_debug;
+
+/// The options sent to the GUI after `uci`.
+///
+/// These are the options of the engine and its registry, followed by `Debug Log File`.
+pub(crate) fn declared_options<E>(engine: &mut E) -> Vec<EngineOption>
+where
+    E: UciEngine,
+{
+    let mut options = engine.options();
+
+    if let Some(registry) = engine.option_registry() {
+        options.extend(registry.options());
+    }
+
+    options.push(EngineOption::debug_log_file());
+    options
+}
+
+/// Validate the value of a declared option and pass it to the engine, like `setoption`.
+///
+/// The name is not case-sensitive and spaces can be replaced by `_`.
+/// The value is validated by the [`UciEngine::option_registry`] if it contains the option,
+/// otherwise against the type of the declared option.
+/// The `Debug Log File` is not passed to the engine, but opened and passed to `set_transcript`.
+pub(crate) fn set_option<E, F>(
+    engine: &mut E,
+    name: &str,
+    value: Option<&str>,
+    set_transcript: F,
+) -> Result<(), OptionError>
+where
+    E: UciEngine,
+    F: FnOnce(Option<File>),
+{
+    let option = declared_options(engine)
+        .into_iter()
+        .find(|option| flag_name(&option.name).eq_ignore_ascii_case(&flag_name(name)))
+        .ok_or_else(|| OptionError::UnknownName(name.to_string()))?;
+    let invalid_value = || OptionError::InvalidValue {
+        name: option.name.clone(),
+        value: value.map(str::to_string),
+    };
+
+    let value = match engine
+        .option_registry()
+        .filter(|registry| registry.contains(&option.name))
+    {
+        Some(registry) => registry.set(&option.name, value)?.1.clone(),
+        None => OptionValue::parse(&option.r#type, value).ok_or_else(invalid_value)?,
+    };
+
+    if option.name == EngineOption::DEBUG_LOG_FILE {
+        let file = open_transcript(Path::new(&value.to_string())).map_err(|_| invalid_value())?;
+        set_transcript(file);
+        return Ok(());
+    }
+
+    let value = (value != OptionValue::Button).then(|| value.to_string());
+    engine.set_option(&option.name, value.as_deref());
+    Ok(())
+}
+
+/// The name of the option in flags and environment variables, with `_` instead of spaces.
+pub(crate) fn flag_name(name: &str) -> String {
+    name.replace(' ', "_")
+}
+
+/// Parse a line sent by the GUI.
+///
+/// Returns `None` if the line is empty.
+/// Lines without a UCI command are returned as [`CustomCommand`](super::CustomCommand),
+/// they must be ignored if the engine doesn't know them.
+/// In debug mode, the received line is echoed to the GUI.
+pub(crate) fn parse_line(writer: &UciWriter, line: &str) -> Option<Input> {
+    writer.log_received(line);
+    let line = line.trim();
+
+    if line.is_empty() {
+        return None;
+    }
+
+    uci_debug!(writer, "received {line}");
+
+    Some(parse_input(line))
+}
+
+/// What the engine loop has to do after a command has been handled.
+pub(crate) enum Next {
+    /// Continue with the next command.
+    Continue,
+
+    /// Start a search.
+    Search(GoCommand),
+
+    /// Quit the engine.
+    Quit,
+}
+
+/// The state of the communication with the GUI, shared by all engine loops.
+pub(crate) struct Session {
+    /// The writer to send commands to the GUI.
+    pub writer: UciWriter,
+
+    /// The variant selected by `UCI_Variant`.
+    variant: Variant,
+
+    /// The position of the last `position` command, used by the non-standard commands.
+    position: CurrentPosition,
+
+    /// Holds back the best move after `go ponder` and `go infinite`,
+    /// until `stop` or `ponderhit` is received.
+    ponder: SharedPonder,
+}
+
+impl Session {
+    /// Create a new session sending the commands to the given output.
+    ///
+    /// The communication is logged to the `Debug Log File` opened by [`configure`], if any.
+    ///
+    /// [`configure`]: super::configure
+    pub fn new<W>(output: W, debug_log_file: Option<File>) -> Self
+    where
+        W: Write + Send + 'static,
+    {
+        let writer = UciWriter::new(output);
+        writer.set_transcript_file(debug_log_file);
+
+        #[cfg(feature = "log")]
+        super::logger::set_writer(&writer);
+
+        Self {
+            writer,
+            variant: Variant::Standard,
+            position: CurrentPosition::new(),
+            ponder: SharedPonder::default(),
+        }
+    }
+
+    /// Create the context of a search started with `go`.
+    pub fn search_context(&self, go: &GoCommand) -> io::Result<SearchContext> {
+        SearchContext::new(self.writer.clone()).with_ponder(self.ponder.clone(), go)
+    }
+
+    /// Send the best move that is still held back, because the engine quits.
+    pub fn release_best_move(&self) -> io::Result<()> {
+        match self.ponder.stop() {
+            Ok(Some(best_move)) => self.send(EngineToGuiCommand::BestMove(best_move)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Parse a line sent by the GUI.
+    ///
+    /// Returns `None` if the line is empty.
+    pub fn parse(&mut self, line: &str) -> Option<Input> {
+        parse_line(&self.writer, line)
+    }
+
+    /// Handle a command that is not received during a search.
+    ///
+    /// Searches are not started here, because every loop runs them differently.
+    pub fn handle<E>(&mut self, engine: &mut E, input: Input) -> io::Result<Next>
+    where
+        E: UciEngine,
+    {
+        let command = match input {
+            Input::Uci(command) => command,
+            Input::Custom(command) => {
+                custom::handle(
+                    engine,
+                    &self.writer,
+                    self.variant,
+                    &mut self.position,
+                    command,
+                )?;
+                return Ok(Next::Continue);
+            }
+        };
+
+        match command {
+            GuiToEngineCommand::Uci => {
+                self.send(EngineToGuiCommand::Id(IdCommand::new_name(engine.name())))?;
+                self.send(EngineToGuiCommand::Id(IdCommand::new_author(
+                    engine.author(),
+                )))?;
+
+                for option in declared_options(engine) {
+                    self.send(EngineToGuiCommand::Option(option))?;
+                }
+
+                self.send(EngineToGuiCommand::UciOk)?;
+
+                if engine.is_copy_protected() {
+                    self.send(EngineToGuiCommand::CopyProtection(
+                        CopyProtectionCommand::Checking,
+                    ))?;
+
+                    let status = if engine.check_copy_protection() {
+                        CopyProtectionCommand::Ok
+                    } else {
+                        CopyProtectionCommand::Error
+                    };
+                    self.send(EngineToGuiCommand::CopyProtection(status))?;
+                }
+
+                if engine.needs_registration() {
+                    self.send_registration(|| engine.check_registration())?;
+                }
+            }
+            GuiToEngineCommand::Debug(debug) => {
+                self.writer.set_debug(debug);
+                engine.debug(debug);
+                uci_debug!(self.writer, "debug mode on");
+            }
+            GuiToEngineCommand::IsReady => self.send(EngineToGuiCommand::ReadyOk)?,
+            GuiToEngineCommand::SetOption(set_option) => self.set_option(engine, set_option)?,
+            GuiToEngineCommand::Register(register) if engine.needs_registration() => match register
+            {
+                RegisterCommand::Later => engine.register_later(),
+                RegisterCommand::Register { name, code } => {
+                    self.send_registration(|| engine.register(&name, &code))?
+                }
+            },
+            GuiToEngineCommand::Register(_) => {}
+            GuiToEngineCommand::UciNewGame => engine.new_game(),
+            GuiToEngineCommand::Position(position) => {
+                engine.position(&position);
+                self.position.set(self.variant, position);
+
+                if let Err(error) = &self.position.board {
+                    uci_debug!(self.writer, "{error}");
+                }
+            }
+            GuiToEngineCommand::Go(go) => return Ok(Next::Search(go)),
+            GuiToEngineCommand::Stop => match self.ponder.stop() {
+                Ok(Some(best_move)) => self.send(EngineToGuiCommand::BestMove(best_move))?,
+                _ => engine.stop(),
+            },
+            GuiToEngineCommand::PonderHit => match self.ponder.ponder_hit() {
+                Ok(Some(best_move)) => self.send(EngineToGuiCommand::BestMove(best_move))?,
+                _ => engine.ponder_hit(),
+            },
+            GuiToEngineCommand::Quit => return Ok(Next::Quit),
+        }
+
+        Ok(Next::Continue)
+    }
+
+    /// Set the option, validating the value against the declared options.
+    ///
+    /// Invalid values are reported to the GUI and not passed to the engine,
+    /// options that haven't been declared are passed to the engine unchanged.
+    /// The `UCI_Variant` is remembered for the next `position` command.
+    fn set_option<E>(&mut self, engine: &mut E, command: SetOptionCommand) -> io::Result<()>
+    where
+        E: UciEngine,
+    {
+        let writer = &self.writer;
+
+        match set_option(engine, &command.name, command.value.as_deref(), |file| {
+            writer.set_transcript_file(file)
+        }) {
+            Ok(()) => uci_debug!(self.writer, "option {} set", command.name),
+            Err(OptionError::UnknownName(_)) => {
+                engine.set_option(&command.name, command.value.as_deref())
+            }
+            Err(error) => {
+                return self
+                    .writer
+                    .send_info(InfoCommand::new().with_string(error.to_string()))
+            }
+        }
+
+        self.set_variant(&command);
+        Ok(())
+    }
+
+    /// Remember the variant if the option is `UCI_Variant`.
+    fn set_variant(&mut self, set_option: &SetOptionCommand) {
+        if let Some(variant) = set_option
+            .variant()
+            .filter(|_| set_option.is(EngineOption::VARIANT))
+        {
+            self.variant = variant;
+        }
+    }
+
+    /// Check the registration and send the result to the GUI.
+    fn send_registration<F>(&self, check: F) -> io::Result<()>
+    where
+        F: FnOnce() -> bool,
+    {
+        self.send(EngineToGuiCommand::Registration(
+            RegistrationCommand::Checking,
+        ))?;
+
+        let status = if check() {
+            RegistrationCommand::Ok
+        } else {
+            RegistrationCommand::Error
+        };
+        self.send(EngineToGuiCommand::Registration(status))
+    }
+
+    /// Send a command to the GUI.
+    pub fn send(&self, command: EngineToGuiCommand) -> io::Result<()> {
+        self.writer.send(&command)
+    }
+}
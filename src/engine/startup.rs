@@ -0,0 +1,329 @@
+use std::fs::File;
+
+use crate::command::engine_to_gui::OptionType;
+
+use super::{
+    session::{declared_options, flag_name, set_option},
+    OptionError, UciEngine,
+};
+
+/// The prefix of the environment variables that set options, e.g. `UCI_OPT_Hash=256`.
+pub const OPTION_VAR_PREFIX: &str = "UCI_OPT_";
+
+/// What the engine should do after [`configure`].
+#[derive(Debug)]
+pub enum Startup {
+    /// Start the engine loop.
+    Run {
+        /// The arguments that are not options, e.g. `bench`.
+        args: Vec<String>,
+
+        /// The `Debug Log File` opened by the options, to be passed to the engine loop.
+        debug_log_file: Option<File>,
+
+        /// The environment variables that have been skipped,
+        /// because the engine has no option with their name.
+        warnings: Vec<OptionError>,
+    },
+
+    /// Print the given help and exit, because `--help` has been passed.
+    Help(String),
+}
+
+/// Set the options of the engine from the command-line arguments and environment variables,
+/// before the GUI sends `uci`.
+///
+/// Options are set with `--<name>=<value>` or `UCI_OPT_<name>=<value>`, e.g. `--Hash=256`.
+/// Spaces in the name can be replaced by `_`, e.g. `--Clear_Hash` for the button `Clear Hash`,
+/// and like in `setoption` the names are not case-sensitive.
+/// The arguments are applied after the environment variables, so they take precedence.
+///
+/// The values are validated like the values of `setoption`, against the declared
+/// [`UciEngine::options`] and the [`UciEngine::option_registry`].
+/// The first invalid value or unknown argument is returned as error.
+/// Like `setoption` with an unknown name, unknown environment variables don't stop the engine,
+/// they are returned as warnings instead.
+///
+/// The `Debug Log File` is opened right away and has to be passed to the engine loop.
+///
+/// ```no_run
+/// # use universal_chess_interface::engine::{configure, run, Startup, UciEngine};
+/// # fn main_with<E: UciEngine>(engine: &mut E) {
+/// match configure(engine, std::env::args().skip(1), std::env::vars()) {
+///     Ok(Startup::Run {
+///         debug_log_file,
+///         warnings,
+///         ..
+///     }) => {
+///         for warning in warnings {
+///             eprintln!("warning: {warning}");
+///         }
+///
+///         run(engine, std::io::stdin().lock(), std::io::stdout(), debug_log_file).unwrap();
+///     }
+///     Ok(Startup::Help(help)) => print!("{help}"),
+///     Err(error) => eprintln!("{error}"),
+/// }
+/// # }
+/// ```
+pub fn configure<E, A, V>(engine: &mut E, args: A, vars: V) -> Result<Startup, OptionError>
+where
+    E: UciEngine,
+    A: IntoIterator<Item = String>,
+    V: IntoIterator<Item = (String, String)>,
+{
+    let args: Vec<_> = args.into_iter().collect();
+
+    if args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        return Ok(Startup::Help(help(engine)));
+    }
+
+    let mut debug_log_file = None;
+    let mut warnings = Vec::new();
+
+    for (var, value) in vars {
+        let Some(name) = var.strip_prefix(OPTION_VAR_PREFIX) else {
+            continue;
+        };
+
+        match set_option(engine, name, Some(&value), |file| debug_log_file = file) {
+            Err(warning @ OptionError::UnknownName(_)) => warnings.push(warning),
+            result => result?,
+        }
+    }
+
+    let mut rest = Vec::new();
+
+    for arg in args {
+        let Some(option) = arg.strip_prefix("--") else {
+            rest.push(arg);
+            continue;
+        };
+
+        let (name, value) = match option.split_once('=') {
+            Some((name, value)) => (name, Some(value)),
+            None => (option, None),
+        };
+        set_option(engine, name, value, |file| debug_log_file = file)?;
+    }
+
+    Ok(Startup::Run {
+        args: rest,
+        debug_log_file,
+        warnings,
+    })
+}
+
+/// List all options of the engine with their type, range and default.
+pub fn help<E>(engine: &mut E) -> String
+where
+    E: UciEngine,
+{
+    let options: Vec<_> = declared_options(engine)
+        .into_iter()
+        .map(|option| {
+            let flag = match option.r#type {
+                OptionType::Button => format!("--{}", flag_name(&option.name)),
+                _ => format!("--{}=<value>", flag_name(&option.name)),
+            };
+
+            (flag, option.r#type.to_string())
+        })
+        .collect();
+
+    let width = options
+        .iter()
+        .map(|(flag, _)| flag.len())
+        .max()
+        .unwrap_or(0);
+    let mut help = format!(
+        "{} by {}\n\nOptions, also settable with {OPTION_VAR_PREFIX}<name>=<value>:\n",
+        engine.name(),
+        engine.author()
+    );
+
+    for (flag, r#type) in options {
+        help.push_str(&format!("  {flag:<width$}  {type}\n"));
+    }
+
+    help.push_str(&format!("  {:<width$}  show this help\n", "--help"));
+    help
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        command::engine_to_gui::{ComboOption, EngineOption},
+        engine::{run, test_utils::TestEngine, OptionRegistry},
+    };
+    use std::{fs, io};
+
+    fn engine() -> TestEngine {
+        TestEngine {
+            registry: Some(
+                OptionRegistry::new()
+                    .with_option(EngineOption::new(
+                        "Style",
+                        OptionType::Combo(ComboOption::new(["Solid", "Risky"], "Solid")),
+                    ))
+                    .with_option(EngineOption::clear_hash()),
+            ),
+            ..Default::default()
+        }
+    }
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn set_options_from_args_and_vars() {
+        let mut engine = engine();
+
+        let startup = configure(
+            &mut engine,
+            strings(&["--hash=256", "bench", "--Clear_Hash", "--Style=risky"]),
+            [
+                ("UCI_OPT_Hash".to_string(), "32".to_string()),
+                ("HOME".to_string(), "/root".to_string()),
+            ],
+        );
+
+        let Ok(Startup::Run {
+            args,
+            debug_log_file,
+            warnings,
+        }) = startup
+        else {
+            panic!("unexpected startup {startup:?}");
+        };
+        assert_eq!(args, ["bench"]);
+        assert!(debug_log_file.is_none());
+        assert!(warnings.is_empty());
+        assert_eq!(
+            engine.received,
+            [
+                "setoption name Hash value 32",
+                "setoption name Hash value 256",
+                "setoption name Clear Hash",
+                "setoption name Style value Risky",
+            ]
+        );
+        assert_eq!(engine.registry.unwrap().combo("Style"), Some("Risky"));
+    }
+
+    #[test]
+    fn reject_invalid_options() {
+        let mut engine = engine();
+
+        assert_eq!(
+            configure(&mut engine, strings(&["--Hash=0"]), []).unwrap_err(),
+            (OptionError::InvalidValue {
+                name: "Hash".to_string(),
+                value: Some("0".to_string())
+            })
+        );
+        assert_eq!(
+            configure(&mut engine, strings(&["--Style=Wild"]), []).unwrap_err(),
+            (OptionError::InvalidValue {
+                name: "Style".to_string(),
+                value: Some("Wild".to_string())
+            })
+        );
+        assert_eq!(
+            configure(&mut engine, strings(&["--Threads=4"]), []).unwrap_err(),
+            (OptionError::UnknownName("Threads".to_string()))
+        );
+        assert_eq!(
+            configure(&mut engine, strings(&["--Debug_Log_File=/"]), []).unwrap_err(),
+            (OptionError::InvalidValue {
+                name: "Debug Log File".to_string(),
+                value: Some("/".to_string())
+            })
+        );
+        assert!(engine.received.is_empty());
+    }
+
+    #[test]
+    fn skip_unknown_option_vars() {
+        let mut engine = engine();
+
+        let startup = configure(
+            &mut engine,
+            [],
+            [
+                ("UCI_OPT_Threads".to_string(), "4".to_string()),
+                ("UCI_OPT_Hash".to_string(), "64".to_string()),
+            ],
+        );
+
+        let Ok(Startup::Run { args, warnings, .. }) = startup else {
+            panic!("unexpected startup {startup:?}");
+        };
+        assert!(args.is_empty());
+        assert_eq!(warnings, [OptionError::UnknownName("Threads".to_string())]);
+        assert_eq!(engine.received, ["setoption name Hash value 64"]);
+    }
+
+    #[test]
+    fn log_to_debug_log_file_from_startup() {
+        let path =
+            std::env::temp_dir().join(format!("uci-transcript-{}-startup.log", std::process::id()));
+        fs::remove_file(&path).ok();
+        let mut engine = engine();
+
+        let startup = configure(
+            &mut engine,
+            [],
+            [(
+                "UCI_OPT_Debug_Log_File".to_string(),
+                path.display().to_string(),
+            )],
+        );
+        let Ok(Startup::Run { debug_log_file, .. }) = startup else {
+            panic!("unexpected startup {startup:?}");
+        };
+        run(
+            &mut engine,
+            "isready\nquit\n".as_bytes(),
+            io::sink(),
+            debug_log_file,
+        )
+        .unwrap();
+
+        let transcript = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert!(engine.received.is_empty());
+        // Skip the timestamps
+        let lines: Vec<_> = transcript
+            .lines()
+            .map(|line| line.split_once(' ').unwrap().1)
+            .collect();
+        assert_eq!(lines, ["in  isready", "out readyok", "in  quit"]);
+    }
+
+    #[test]
+    fn show_help() {
+        let mut engine = engine();
+
+        let startup = configure(&mut engine, strings(&["--Hash=256", "--help"]), []);
+
+        let Ok(Startup::Help(help)) = startup else {
+            panic!("unexpected startup {startup:?}");
+        };
+        assert_eq!(
+            help,
+            "Test Engine by Test Author\n\
+                \n\
+                Options, also settable with UCI_OPT_<name>=<value>:\n  \
+                --Hash=<value>            spin default 16 min 1 max 1024\n  \
+                --Style=<value>           combo default Solid var Solid var Risky\n  \
+                --Clear_Hash              button\n  \
+                --Debug_Log_File=<value>  string default <empty>\n  \
+                --help                    show this help\n"
+        );
+        assert!(engine.received.is_empty());
+    }
+}
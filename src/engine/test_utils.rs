@@ -0,0 +1,209 @@
+//! Helpers to test the engine framework.
+
+use std::{
+    io::{self, Write},
+    sync::{Arc, Mutex},
+};
+
+use crate::{
+    chess::{Evaluation, Move, MoveLine},
+    command::{
+        engine_to_gui::{BestMoveCommand, EngineOption, InfoCommand},
+        gui_to_engine::{GoCommand, PositionCommand},
+    },
+};
+
+use super::{run, OptionRegistry, SearchContext, UciEngine, UciWriter};
+
+/// An output that can be inspected after it has been moved into the engine loop.
+#[derive(Clone, Default)]
+pub struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl SharedBuffer {
+    /// The lines that have been written so far.
+    pub fn lines(&self) -> Vec<String> {
+        String::from_utf8(self.0.lock().unwrap().clone())
+            .unwrap()
+            .lines()
+            .map(str::to_string)
+            .collect()
+    }
+}
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// An engine that records the commands it receives and always plays `e7e5`.
+#[derive(Default)]
+pub struct TestEngine {
+    /// The commands passed to the engine, formatted as UCI commands.
+    pub received: Vec<String>,
+
+    /// The registry of additional options, if any.
+    pub registry: Option<OptionRegistry>,
+
+    /// The result of the copy protection check, if the engine is copy protected.
+    pub copy_protection: Option<bool>,
+
+    /// The valid registration code, if the engine needs registration.
+    pub registration_code: Option<String>,
+
+    /// Panic during the search, after sending the given line.
+    pub panic_after: Option<MoveLine>,
+
+    /// The move to play if the search panics.
+    pub fallback_move: Option<Move>,
+
+    /// The static evaluation of every position.
+    pub eval: Option<Evaluation>,
+
+    /// The number of nodes to report after every search.
+    pub node_count: Option<u64>,
+
+    /// Set when the GUI has turned on the debug mode.
+    pub debug: bool,
+
+    /// Return from `go ponder` and `go infinite` right away, without waiting for `stop`.
+    pub return_early: bool,
+}
+
+impl UciEngine for TestEngine {
+    fn name(&self) -> String {
+        "Test Engine".to_string()
+    }
+
+    fn author(&self) -> String {
+        "Test Author".to_string()
+    }
+
+    fn options(&self) -> Vec<EngineOption> {
+        vec![EngineOption::hash(1, 1024, 16)]
+    }
+
+    fn is_copy_protected(&self) -> bool {
+        self.copy_protection.is_some()
+    }
+
+    fn check_copy_protection(&mut self) -> bool {
+        self.copy_protection == Some(true)
+    }
+
+    fn debug(&mut self, enabled: bool) {
+        self.debug = enabled;
+    }
+
+    fn needs_registration(&self) -> bool {
+        self.registration_code.is_some()
+    }
+
+    fn register(&mut self, name: &str, code: &str) -> bool {
+        self.received
+            .push(format!("register name {name} code {code}"));
+        self.registration_code.as_deref() == Some(code)
+    }
+
+    fn register_later(&mut self) {
+        self.received.push("register later".to_string());
+    }
+
+    fn option_registry(&mut self) -> Option<&mut OptionRegistry> {
+        self.registry.as_mut()
+    }
+
+    fn set_option(&mut self, name: &str, value: Option<&str>) {
+        match value {
+            Some(value) => self
+                .received
+                .push(format!("setoption name {name} value {value}")),
+            None => self.received.push(format!("setoption name {name}")),
+        }
+    }
+
+    fn new_game(&mut self) {
+        self.received.push("ucinewgame".to_string());
+    }
+
+    fn position(&mut self, position: &PositionCommand) {
+        self.received.push(position.to_string());
+    }
+
+    fn go(&mut self, go: &GoCommand, search: &SearchContext) -> BestMoveCommand {
+        self.received.push(go.to_string());
+
+        // Search until the GUI wants us to stop
+        if (go.infinite || go.ponder) && !self.return_early {
+            loop {
+                if go.ponder && search.is_ponder_hit() {
+                    self.received.push("ponderhit".to_string());
+                    break;
+                }
+
+                if search.poll() {
+                    break;
+                }
+
+                std::thread::sleep(std::time::Duration::from_millis(1));
+            }
+        }
+
+        let info = InfoCommand::new().with_depth_plies(1);
+        let info = match self.node_count {
+            Some(node_count) => info.with_node_count(node_count),
+            None => info,
+        };
+        search.send_info(info).unwrap();
+
+        if let Some(pv) = self.panic_after.clone() {
+            if !pv.is_empty() {
+                search.send_info(InfoCommand::new().with_pv(pv)).unwrap();
+            }
+            panic!("search exploded");
+        }
+
+        BestMoveCommand::new("e7e5".parse().unwrap())
+    }
+
+    fn fallback_move(&mut self) -> Option<Move> {
+        self.fallback_move
+    }
+
+    fn evaluate(&mut self) -> Option<Evaluation> {
+        self.eval
+    }
+
+    fn custom_command(&mut self, command: &str, writer: &UciWriter) -> bool {
+        if command != "hello" {
+            return false;
+        }
+
+        self.received.push(command.to_string());
+        writer.send_line("hello there").unwrap();
+        true
+    }
+
+    fn stop(&mut self) {
+        self.received.push("stop".to_string());
+    }
+
+    fn ponder_hit(&mut self) {
+        self.received.push("ponderhit".to_string());
+    }
+}
+
+/// Run the engine on the given input and return it together with the lines it sent.
+pub fn run_engine<E>(mut engine: E, input: &str) -> (E, Vec<String>)
+where
+    E: UciEngine,
+{
+    let output = SharedBuffer::default();
+    run(&mut engine, input.as_bytes(), output.clone(), None).unwrap();
+
+    (engine, output.lines())
+}
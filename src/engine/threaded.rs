@@ -0,0 +1,344 @@
+use std::{
+    fs::File,
+    io::{self, BufRead, Lines, Write},
+    panic, thread,
+};
+
+use crate::command::{
+    engine_to_gui::EngineToGuiCommand,
+    gui_to_engine::{GoCommand, GuiToEngineCommand},
+};
+
+use super::{
+    session::{Next, Session},
+    CustomCommand, Input, SearchContext, UciEngine,
+};
+use crate::uci_debug;
+
+/// How the engine loop continues after a search.
+enum AfterSearch {
+    /// Continue reading commands, but handle the given command first.
+    Continue(Option<Input>),
+
+    /// Quit the engine.
+    Quit,
+}
+
+/// Run the engine, searching on a separate thread.
+///
+/// Like [`run`](super::run), but the commands are still read while the engine is searching:
+///
+/// - `isready` is answered immediately, without stopping the search.
+/// - `stop` and `ponderhit` are passed to the search via the [`SearchContext`],
+///   not via [`UciEngine::stop`] and [`UciEngine::ponder_hit`].
+/// - `debug` turns the debug mode on or off immediately,
+///   but [`UciEngine::debug`] is only called after the search has finished.
+/// - `quit` and the end of the input stop the search and wait for it to finish.
+/// - Any other command stops the search and is handled after the search has finished.
+///
+/// Exactly one `bestmove` is sent for every `go`.
+/// After `go ponder` and `go infinite`, it's held back until `stop` or `ponderhit` is received,
+/// even if the engine has finished the search earlier.
+pub fn run_threaded<E, R, W>(
+    engine: &mut E,
+    input: R,
+    output: W,
+    debug_log_file: Option<File>,
+) -> io::Result<()>
+where
+    E: UciEngine + Send,
+    R: BufRead,
+    W: Write + Send + 'static,
+{
+    let mut session = Session::new(output, debug_log_file);
+    let mut lines = input.lines();
+    let mut pending = None;
+
+    loop {
+        let command = match pending.take() {
+            Some(command) => command,
+            None => match next_command(&mut session, &mut lines)? {
+                Some(command) => command,
+                None => break,
+            },
+        };
+
+        match session.handle(engine, command)? {
+            Next::Continue => {}
+            Next::Search(go) => match search(engine, &mut session, &mut lines, &go)? {
+                AfterSearch::Continue(command) => pending = command,
+                AfterSearch::Quit => break,
+            },
+            Next::Quit => break,
+        }
+    }
+
+    Ok(())
+}
+
+/// Read the next known command.
+///
+/// Returns `None` when the input has ended.
+fn next_command<R>(session: &mut Session, lines: &mut Lines<R>) -> io::Result<Option<Input>>
+where
+    R: BufRead,
+{
+    for line in lines {
+        if let Some(command) = session.parse(&line?) {
+            return Ok(Some(command));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Run the search on a worker thread, while handling the commands received in the meantime.
+fn search<E, R>(
+    engine: &mut E,
+    session: &mut Session,
+    lines: &mut Lines<R>,
+    go: &GoCommand,
+) -> io::Result<AfterSearch>
+where
+    E: UciEngine + Send,
+    R: BufRead,
+{
+    let context = session.search_context(go)?;
+    uci_debug!(session.writer, "search started");
+
+    let mut debug = None;
+
+    let result = thread::scope(|scope| {
+        let worker_context = context.clone();
+        let worker_engine = &mut *engine;
+        let worker = scope.spawn(move || worker_context.run(worker_engine, go));
+
+        let result = handle_commands_while_searching(session, lines, &context, &mut debug, || {
+            worker.is_finished()
+        });
+
+        // Make sure that the worker finishes, even if reading the commands failed
+        let stopped = context.stop_search();
+
+        match worker.join() {
+            Ok(sent) => sent?,
+            Err(payload) => panic::resume_unwind(payload),
+        }
+
+        stopped?;
+        result
+    });
+
+    if let Some(debug) = debug {
+        engine.debug(debug);
+    }
+
+    result
+}
+
+/// Handle the commands received during the search, until the search has to end.
+///
+/// The last debug mode received is stored in `debug`, to pass it to the engine after the search.
+fn handle_commands_while_searching<R, F>(
+    session: &mut Session,
+    lines: &mut Lines<R>,
+    context: &SearchContext,
+    debug: &mut Option<bool>,
+    is_finished: F,
+) -> io::Result<AfterSearch>
+where
+    R: BufRead,
+    F: Fn() -> bool,
+{
+    while !is_finished() || context.is_best_move_held() {
+        let Some(command) = next_command(session, lines)? else {
+            return Ok(AfterSearch::Quit);
+        };
+
+        match command {
+            Input::Uci(GuiToEngineCommand::IsReady) => session.send(EngineToGuiCommand::ReadyOk)?,
+            Input::Uci(GuiToEngineCommand::Stop) => {
+                uci_debug!(session.writer, "stopping search");
+                context.stop_search()?;
+            }
+            Input::Uci(GuiToEngineCommand::PonderHit) => context.set_ponder_hit()?,
+            Input::Uci(GuiToEngineCommand::Debug(on)) => {
+                session.writer.set_debug(on);
+                *debug = Some(on);
+                uci_debug!(session.writer, "debug mode on");
+            }
+            Input::Custom(CustomCommand::Other(line)) => {
+                uci_debug!(session.writer, "ignored unknown command {line}");
+            }
+            Input::Uci(GuiToEngineCommand::Quit) => {
+                uci_debug!(session.writer, "stopping search to quit");
+                return Ok(AfterSearch::Quit);
+            }
+            command => {
+                uci_debug!(session.writer, "stopping search to handle {command}");
+                return Ok(AfterSearch::Continue(Some(command)));
+            }
+        }
+    }
+
+    Ok(AfterSearch::Continue(None))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::test_utils::{SharedBuffer, TestEngine};
+
+    fn run_threaded_engine(input: &str) -> (TestEngine, Vec<String>) {
+        let mut engine = TestEngine::default();
+        let output = SharedBuffer::default();
+        run_threaded(&mut engine, input.as_bytes(), output.clone(), None).unwrap();
+
+        (engine, output.lines())
+    }
+
+    #[test]
+    fn answer_is_ready_during_search() {
+        let (engine, output) = run_threaded_engine("go infinite\nisready\nstop\nquit\n");
+
+        assert_eq!(output, ["readyok", "info depth 1", "bestmove e7e5"]);
+        assert_eq!(engine.received, ["go infinite"]);
+    }
+
+    #[test]
+    fn switch_debug_mode_during_search() {
+        let (engine, output) = run_threaded_engine("go infinite\ndebug on\nisready\nstop\n");
+
+        assert_eq!(
+            output,
+            [
+                "info string debug mode on",
+                "info string received isready",
+                "readyok",
+                "info string received stop",
+                "info string stopping search",
+                "info depth 1",
+                "bestmove e7e5",
+            ]
+        );
+        assert!(engine.debug);
+        assert_eq!(engine.received, ["go infinite"]);
+    }
+
+    #[test]
+    fn hold_back_best_move_until_stop() {
+        let mut engine = TestEngine {
+            return_early: true,
+            ..Default::default()
+        };
+        let output = SharedBuffer::default();
+        run_threaded(
+            &mut engine,
+            "go ponder\nisready\nstop\n".as_bytes(),
+            output.clone(),
+            None,
+        )
+        .unwrap();
+
+        let output = output.lines();
+        assert!(output.contains(&"readyok".to_string()));
+        assert_eq!(output.last().unwrap(), "bestmove e7e5");
+        assert_eq!(
+            output
+                .iter()
+                .filter(|line| line.starts_with("bestmove"))
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn finish_search_on_end_of_input() {
+        let (_, output) = run_threaded_engine("go infinite\n");
+
+        assert_eq!(output, ["info depth 1", "bestmove e7e5"]);
+    }
+
+    #[test]
+    fn finish_search_on_quit() {
+        let (_, output) = run_threaded_engine("go infinite\nquit\nisready\n");
+
+        assert_eq!(output, ["info depth 1", "bestmove e7e5"]);
+    }
+
+    #[test]
+    fn recover_from_panicking_search() {
+        let mut engine = TestEngine {
+            panic_after: Some("e2e4".parse().unwrap()),
+            ..Default::default()
+        };
+        let output = SharedBuffer::default();
+        run_threaded(
+            &mut engine,
+            "go infinite\nstop\ngo depth 1\n".as_bytes(),
+            output.clone(),
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(
+            output.lines(),
+            [
+                "info depth 1",
+                "info multipv 1 pv e2e4",
+                "info string search panicked: search exploded",
+                "bestmove e2e4",
+                "info depth 1",
+                "info multipv 1 pv e2e4",
+                "info string search panicked: search exploded",
+                "bestmove e2e4",
+            ]
+        );
+        assert_eq!(engine.received, ["go infinite", "go depth 1"]);
+    }
+
+    #[test]
+    fn handle_commands_after_search() {
+        let (engine, output) = run_threaded_engine("go infinite\nposition startpos\ngo depth 1\n");
+
+        assert_eq!(
+            output,
+            [
+                "info depth 1",
+                "bestmove e7e5",
+                "info depth 1",
+                "bestmove e7e5"
+            ]
+        );
+        assert_eq!(
+            engine.received,
+            ["go infinite", "position startpos", "go depth 1"]
+        );
+    }
+
+    #[test]
+    fn pass_ponder_hit_to_search() {
+        let (engine, output) = run_threaded_engine("go ponder\nponderhit\n");
+
+        assert_eq!(output, ["info depth 1", "bestmove e7e5"]);
+        assert_eq!(engine.received, ["go ponder", "ponderhit"]);
+    }
+
+    #[test]
+    fn report_stopping_in_debug_mode() {
+        let (_, output) = run_threaded_engine("debug on\ngo infinite\nstop\n");
+
+        assert_eq!(
+            output,
+            [
+                "info string debug mode on",
+                "info string received go infinite",
+                "info string search started",
+                "info string received stop",
+                "info string stopping search",
+                "info depth 1",
+                "bestmove e7e5",
+            ]
+        );
+    }
+}
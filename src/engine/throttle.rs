@@ -0,0 +1,281 @@
+use std::time::{Duration, Instant};
+
+use crate::command::engine_to_gui::InfoCommand;
+
+use super::{Clock, SystemClock};
+
+/// The default minimum time between two coalesced info commands.
+pub const DEFAULT_INFO_INTERVAL: Duration = Duration::from_millis(100);
+
+/// The default time after the start of the search before `currmove` and `currline` are sent.
+///
+/// The UCI protocol suggests to send `currmove` only after one second.
+pub const DEFAULT_INFO_WARM_UP: Duration = Duration::from_secs(1);
+
+/// Limits the number of info commands sent to the GUI.
+///
+/// Info commands with a principal variation or a completed depth, as well as strings,
+/// are always sent immediately, unless they report the current move or line.
+/// Other updates, like the node count or the current move, are coalesced:
+/// at most one is sent per interval and only the latest one is kept.
+/// Updates with `currmove`, `currmovenumber` or `currline` are held back
+/// until the warm-up time has passed, and dropped if the search ends before.
+///
+/// The throttler doesn't send anything itself. The returned commands have to be sent
+/// by the engine, e.g. with [`SearchContext::send_info`](super::SearchContext::send_info).
+/// Call [`InfoThrottler::poll`] regularly to send held back updates in time
+/// and [`InfoThrottler::flush`] before sending `bestmove`.
+#[derive(Debug, Clone)]
+pub struct InfoThrottler<C = SystemClock> {
+    /// The clock to measure the time.
+    clock: C,
+
+    /// The minimum time between two coalesced info commands.
+    interval: Duration,
+
+    /// The time after the start of the search before `currmove` and `currline` are sent.
+    warm_up: Duration,
+
+    /// The time when the search has been started.
+    start: Instant,
+
+    /// The time when the last info command has been sent.
+    last_sent: Option<Instant>,
+
+    /// The latest update that has been held back.
+    pending: Option<InfoCommand>,
+}
+
+impl InfoThrottler<SystemClock> {
+    /// Create a new throttler using the system clock, starting now.
+    pub fn new() -> Self {
+        Self::with_clock(SystemClock)
+    }
+}
+
+impl Default for InfoThrottler<SystemClock> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C> InfoThrottler<C>
+where
+    C: Clock,
+{
+    /// Create a new throttler using the given clock, starting now.
+    pub fn with_clock(clock: C) -> Self {
+        let start = clock.now();
+
+        Self {
+            clock,
+            interval: DEFAULT_INFO_INTERVAL,
+            warm_up: DEFAULT_INFO_WARM_UP,
+            start,
+            last_sent: None,
+            pending: None,
+        }
+    }
+
+    /// Set the minimum time between two coalesced info commands.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Set the time after the start of the search before `currmove` and `currline` are sent.
+    pub fn with_warm_up(mut self, warm_up: Duration) -> Self {
+        self.warm_up = warm_up;
+        self
+    }
+
+    /// Reset the throttler for a new search.
+    pub fn start(&mut self) {
+        self.start = self.clock.now();
+        self.last_sent = None;
+        self.pending = None;
+    }
+
+    /// Pass an info command through the throttler.
+    ///
+    /// Returns the command if it should be sent now.
+    /// Otherwise, it's held back and may be returned later by [`InfoThrottler::poll`].
+    pub fn push(&mut self, info: InfoCommand) -> Option<InfoCommand> {
+        let now = self.clock.now();
+
+        if info.string().is_some() {
+            return Some(info);
+        }
+
+        if (info.pv().is_some() || info.depth_plies().is_some()) && !is_current_search(&info) {
+            // The line contains the latest statistics, older updates are obsolete
+            self.pending = None;
+            self.last_sent = Some(now);
+            return Some(info);
+        }
+
+        self.pending = Some(info);
+        self.poll()
+    }
+
+    /// Return the held back update, if it may be sent now.
+    pub fn poll(&mut self) -> Option<InfoCommand> {
+        let now = self.clock.now();
+        let pending = self.pending.as_ref()?;
+
+        if is_current_search(pending) && !self.is_warm() {
+            return None;
+        }
+
+        if self
+            .last_sent
+            .is_some_and(|last_sent| now.saturating_duration_since(last_sent) < self.interval)
+        {
+            return None;
+        }
+
+        self.last_sent = Some(now);
+        self.pending.take()
+    }
+
+    /// Return the held back update, regardless of the interval.
+    ///
+    /// Call this before sending `bestmove`, so the GUI shows the final state of the search.
+    /// Updates of the current move or line are dropped if the warm-up time hasn't passed yet.
+    pub fn flush(&mut self) -> Option<InfoCommand> {
+        let pending = self.pending.take()?;
+
+        (!is_current_search(&pending) || self.is_warm()).then_some(pending)
+    }
+
+    /// Determines if the warm-up time has passed since the start of the search.
+    fn is_warm(&self) -> bool {
+        self.clock.now().saturating_duration_since(self.start) >= self.warm_up
+    }
+}
+
+/// Determines if the info reports the move or line that is currently searched.
+fn is_current_search(info: &InfoCommand) -> bool {
+    info.curr_move().is_some() || info.curr_move_number().is_some() || info.curr_line().is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{chess::Move, engine::ManualClock};
+
+    fn throttler() -> (ManualClock, InfoThrottler<ManualClock>) {
+        let clock = ManualClock::new();
+        let throttler = InfoThrottler::with_clock(clock.clone())
+            .with_interval(Duration::from_millis(100))
+            .with_warm_up(Duration::from_secs(1));
+
+        (clock, throttler)
+    }
+
+    fn nodes(count: u64) -> InfoCommand {
+        InfoCommand::new().with_node_count(count)
+    }
+
+    fn curr_move() -> InfoCommand {
+        InfoCommand::new()
+            .with_curr_move("e2e4".parse::<Move>().unwrap())
+            .with_curr_move_number(1)
+    }
+
+    #[test]
+    fn coalesce_updates_within_interval() {
+        let (clock, mut throttler) = throttler();
+
+        assert_eq!(throttler.push(nodes(1)), Some(nodes(1)));
+        assert_eq!(throttler.push(nodes(2)), None);
+        assert_eq!(throttler.push(nodes(3)), None);
+
+        clock.advance(Duration::from_millis(99));
+        assert_eq!(throttler.poll(), None);
+
+        clock.advance(Duration::from_millis(1));
+        assert_eq!(throttler.poll(), Some(nodes(3)));
+        assert_eq!(throttler.poll(), None);
+    }
+
+    #[test]
+    fn always_pass_depth_and_pv() {
+        let (_, mut throttler) = throttler();
+        let depth = InfoCommand::new().with_depth_plies(5).with_node_count(10);
+        let pv = InfoCommand::new().with_pv(vec!["e2e4".parse::<Move>().unwrap()]);
+        let string = InfoCommand::new().with_string("hello");
+
+        assert_eq!(throttler.push(nodes(1)), Some(nodes(1)));
+        assert_eq!(throttler.push(nodes(2)), None);
+        assert_eq!(throttler.push(depth.clone()), Some(depth));
+        assert_eq!(throttler.push(pv.clone()), Some(pv));
+        assert_eq!(throttler.push(string.clone()), Some(string));
+
+        // The pending update is obsolete
+        assert_eq!(throttler.flush(), None);
+    }
+
+    #[test]
+    fn hold_curr_move_until_warm_up() {
+        let (clock, mut throttler) = throttler();
+
+        assert_eq!(throttler.push(curr_move()), None);
+
+        clock.advance(Duration::from_millis(999));
+        assert_eq!(throttler.poll(), None);
+
+        clock.advance(Duration::from_millis(1));
+        assert_eq!(throttler.poll(), Some(curr_move()));
+        assert_eq!(throttler.push(nodes(1)), None);
+    }
+
+    #[test]
+    fn hold_depth_with_curr_move_until_warm_up() {
+        let (clock, mut throttler) = throttler();
+        let info = curr_move().with_depth_plies(25);
+
+        assert_eq!(throttler.push(info.clone()), None);
+
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(throttler.poll(), Some(info.clone()));
+        assert_eq!(throttler.push(info), None);
+    }
+
+    #[test]
+    fn flush_before_best_move() {
+        let (clock, mut throttler) = throttler();
+
+        clock.advance(Duration::from_secs(1));
+        throttler.push(nodes(1));
+        throttler.push(curr_move());
+
+        assert_eq!(throttler.flush(), Some(curr_move()));
+        assert_eq!(throttler.flush(), None);
+    }
+
+    #[test]
+    fn drop_curr_move_on_flush_before_warm_up() {
+        let (_, mut throttler) = throttler();
+
+        throttler.push(nodes(1));
+        throttler.push(nodes(2));
+        assert_eq!(throttler.flush(), Some(nodes(2)));
+
+        throttler.push(curr_move());
+        assert_eq!(throttler.flush(), None);
+    }
+
+    #[test]
+    fn start_resets_warm_up() {
+        let (clock, mut throttler) = throttler();
+
+        clock.advance(Duration::from_secs(5));
+        throttler.push(nodes(1));
+        throttler.push(nodes(2));
+        throttler.start();
+
+        assert_eq!(throttler.flush(), None);
+        assert_eq!(throttler.push(curr_move()), None);
+    }
+}
@@ -0,0 +1,311 @@
+use std::{
+    sync::{Arc, Mutex, PoisonError},
+    time::{Duration, Instant},
+};
+
+use crate::{chess::Color, command::gui_to_engine::GoCommand};
+
+/// The default time reserved per move for the communication with the GUI.
+pub const DEFAULT_MOVE_OVERHEAD: Duration = Duration::from_millis(10);
+
+/// The number of moves that the remaining time is divided by in sudden death games.
+const SUDDEN_DEATH_MOVES: u32 = 30;
+
+/// The maximum number of moves that the remaining time is divided by in repeating time controls.
+const MAX_MOVES_TO_GO: u32 = 50;
+
+/// A source of the current time.
+///
+/// This allows to test time management deterministically with a [`ManualClock`].
+pub trait Clock {
+    /// The current time.
+    fn now(&self) -> Instant;
+}
+
+/// The clock of the operating system.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// A clock that only advances when told so.
+///
+/// Clones share the same time, so a clone can be advanced while the original is used.
+#[derive(Debug, Clone)]
+pub struct ManualClock {
+    /// The time when the clock was created.
+    origin: Instant,
+
+    /// The time that has passed since the clock was created.
+    elapsed: Arc<Mutex<Duration>>,
+}
+
+impl ManualClock {
+    /// Create a new clock that is stopped at the current time.
+    pub fn new() -> Self {
+        Self {
+            origin: Instant::now(),
+            elapsed: Arc::new(Mutex::new(Duration::ZERO)),
+        }
+    }
+
+    /// Advance the clock by the given duration.
+    pub fn advance(&self, duration: Duration) {
+        *self.elapsed.lock().unwrap_or_else(PoisonError::into_inner) += duration;
+    }
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        self.origin + *self.elapsed.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// The time that the engine may spend on a move.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TimeBudget {
+    /// The time after which no new iteration should be started.
+    pub soft: Duration,
+
+    /// The time after which the search must be stopped.
+    pub hard: Duration,
+}
+
+impl TimeBudget {
+    /// Determine the time budget for a search from the `go` command.
+    ///
+    /// `move_overhead` is reserved per move for the communication with the GUI.
+    /// Returns `None` if the time is not limited, e.g. for `go infinite` or `go depth 5`.
+    ///
+    /// The time of `movetime` is used completely.
+    /// Otherwise, the remaining time of the side to move is divided by the moves to go
+    /// and most of the increment is added on top.
+    /// Without `movestogo`, the game is treated as sudden death.
+    pub fn from_go(go: &GoCommand, side_to_move: Color, move_overhead: Duration) -> Option<Self> {
+        if go.infinite {
+            return None;
+        }
+
+        if let Some(move_time) = go.move_time {
+            let time = move_time.saturating_sub(move_overhead);
+
+            return Some(Self {
+                soft: time,
+                hard: time,
+            });
+        }
+
+        let (time, increment) = match side_to_move {
+            Color::White => (go.white_time?, go.white_increment),
+            Color::Black => (go.black_time?, go.black_increment),
+        };
+        let increment = increment.unwrap_or_default();
+        let available = time.saturating_sub(move_overhead);
+
+        let moves_to_go = go
+            .moves_to_go
+            .unwrap_or(SUDDEN_DEATH_MOVES)
+            .clamp(1, MAX_MOVES_TO_GO);
+
+        // The time control is reset after the last move, so all time can be used
+        let hard = if moves_to_go == 1 {
+            available
+        } else {
+            available * 4 / 5
+        };
+        let soft = (available / moves_to_go + increment * 3 / 4).min(hard);
+        let hard = hard.min(soft * 5);
+
+        Some(Self { soft, hard })
+    }
+}
+
+/// Keeps track of the time spent on the current search.
+///
+/// Call [`TimeManager::start`] when a search starts and check the limits during the search.
+#[derive(Debug, Clone)]
+pub struct TimeManager<C = SystemClock> {
+    /// The clock to measure the time.
+    clock: C,
+
+    /// The time reserved per move for the communication with the GUI.
+    move_overhead: Duration,
+
+    /// The time when the current search has been started.
+    start: Instant,
+
+    /// The time budget of the current search, if the time is limited.
+    budget: Option<TimeBudget>,
+}
+
+impl TimeManager<SystemClock> {
+    /// Create a new time manager using the system clock.
+    pub fn new() -> Self {
+        Self::with_clock(SystemClock)
+    }
+}
+
+impl Default for TimeManager<SystemClock> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C> TimeManager<C>
+where
+    C: Clock,
+{
+    /// Create a new time manager using the given clock.
+    pub fn with_clock(clock: C) -> Self {
+        let start = clock.now();
+
+        Self {
+            clock,
+            move_overhead: DEFAULT_MOVE_OVERHEAD,
+            start,
+            budget: None,
+        }
+    }
+
+    /// Set the time reserved per move for the communication with the GUI.
+    pub fn with_move_overhead(mut self, move_overhead: Duration) -> Self {
+        self.move_overhead = move_overhead;
+        self
+    }
+
+    /// The time reserved per move for the communication with the GUI.
+    pub fn move_overhead(&self) -> Duration {
+        self.move_overhead
+    }
+
+    /// Set the time reserved per move, e.g. when the `Move Overhead` option is changed.
+    pub fn set_move_overhead(&mut self, move_overhead: Duration) {
+        self.move_overhead = move_overhead;
+    }
+
+    /// Start the time measurement for a new search.
+    pub fn start(&mut self, go: &GoCommand, side_to_move: Color) {
+        self.start = self.clock.now();
+        self.budget = TimeBudget::from_go(go, side_to_move, self.move_overhead);
+    }
+
+    /// The time budget of the current search, or `None` if the time is not limited.
+    pub fn budget(&self) -> Option<TimeBudget> {
+        self.budget
+    }
+
+    /// The time spent on the current search.
+    pub fn elapsed(&self) -> Duration {
+        self.clock.now().saturating_duration_since(self.start)
+    }
+
+    /// Determines if no new iteration should be started anymore.
+    pub fn is_soft_limit_reached(&self) -> bool {
+        self.budget
+            .is_some_and(|budget| self.elapsed() >= budget.soft)
+    }
+
+    /// Determines if the search must be stopped.
+    pub fn is_hard_limit_reached(&self) -> bool {
+        self.budget
+            .is_some_and(|budget| self.elapsed() >= budget.hard)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn millis(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[rstest]
+    #[case("go infinite", Color::White, None)]
+    #[case("go depth 5", Color::White, None)]
+    #[case("go wtime 1000", Color::Black, None)]
+    #[case("go movetime 1000", Color::White, Some((990, 990)))]
+    #[case("go movetime 5", Color::White, Some((0, 0)))]
+    // Sudden death
+    #[case("go wtime 60010 btime 1000", Color::White, Some((2000, 10000)))]
+    #[case("go wtime 1000 btime 60010", Color::Black, Some((2000, 10000)))]
+    // Increment
+    #[case(
+        "go wtime 60010 btime 60010 winc 1000 binc 0",
+        Color::White,
+        Some((2750, 13750))
+    )]
+    #[case("go wtime 1010 winc 2000", Color::White, Some((800, 800)))]
+    // Repeating time control
+    #[case("go wtime 60010 movestogo 10", Color::White, Some((6000, 30000)))]
+    #[case("go wtime 60010 movestogo 1", Color::White, Some((60000, 60000)))]
+    #[case("go wtime 60010 movestogo 0", Color::White, Some((60000, 60000)))]
+    // Out of time
+    #[case("go wtime 0 btime 1000", Color::White, Some((0, 0)))]
+    fn time_budget_from_go(
+        #[case] go: GoCommand,
+        #[case] side_to_move: Color,
+        #[case] expected: Option<(u64, u64)>,
+    ) {
+        let expected = expected.map(|(soft, hard)| TimeBudget {
+            soft: millis(soft),
+            hard: millis(hard),
+        });
+
+        assert_eq!(
+            TimeBudget::from_go(&go, side_to_move, DEFAULT_MOVE_OVERHEAD),
+            expected
+        );
+    }
+
+    #[test]
+    fn time_budget_respects_move_overhead() {
+        let go = "go wtime 10000 movestogo 1".parse().unwrap();
+        let budget = TimeBudget::from_go(&go, Color::White, millis(500)).unwrap();
+
+        assert_eq!(budget.hard, millis(9500));
+    }
+
+    #[test]
+    fn time_manager_checks_limits() {
+        let clock = ManualClock::new();
+        let mut time = TimeManager::with_clock(clock.clone()).with_move_overhead(Duration::ZERO);
+
+        clock.advance(millis(5000));
+        time.start(&"go movetime 1000".parse().unwrap(), Color::White);
+
+        assert_eq!(time.elapsed(), Duration::ZERO);
+        assert!(!time.is_soft_limit_reached());
+
+        clock.advance(millis(999));
+        assert!(!time.is_hard_limit_reached());
+
+        clock.advance(millis(1));
+        assert!(time.is_soft_limit_reached());
+        assert!(time.is_hard_limit_reached());
+    }
+
+    #[test]
+    fn time_manager_without_limits() {
+        let clock = ManualClock::new();
+        let mut time = TimeManager::with_clock(clock.clone());
+
+        time.start(&"go infinite".parse().unwrap(), Color::White);
+        clock.advance(Duration::from_secs(3600));
+
+        assert_eq!(time.budget(), None);
+        assert!(!time.is_soft_limit_reached());
+        assert!(!time.is_hard_limit_reached());
+    }
+}
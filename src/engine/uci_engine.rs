@@ -0,0 +1,132 @@
+use crate::{
+    chess::{Evaluation, Move},
+    command::{
+        engine_to_gui::{BestMoveCommand, EngineOption},
+        gui_to_engine::{GoCommand, PositionCommand},
+    },
+};
+
+use super::{OptionRegistry, SearchContext, UciWriter};
+
+/// A chess engine that can be driven by [`run`](super::run).
+///
+/// Only the identification, the position and the search need to be implemented,
+/// all other callbacks do nothing by default.
+pub trait UciEngine {
+    /// The name of the engine, sent as `id name`.
+    fn name(&self) -> String;
+
+    /// The author of the engine, sent as `id author`.
+    fn author(&self) -> String;
+
+    /// The options that the GUI can change, sent after the identification.
+    fn options(&self) -> Vec<EngineOption> {
+        Vec::new()
+    }
+
+    /// Determines if the engine is copy protected.
+    ///
+    /// If so, [`UciEngine::check_copy_protection`] is called after `uciok`.
+    fn is_copy_protected(&self) -> bool {
+        false
+    }
+
+    /// Check the copy protection of the engine.
+    ///
+    /// Returns `false` if the check has failed, the engine should not function properly then.
+    fn check_copy_protection(&mut self) -> bool {
+        true
+    }
+
+    /// Determines if the engine needs to be registered.
+    ///
+    /// If so, [`UciEngine::check_registration`] is called after `uciok`
+    /// and the GUI can register the engine with `register`.
+    fn needs_registration(&self) -> bool {
+        false
+    }
+
+    /// Check if the engine has already been registered, e.g. with a stored registration code.
+    ///
+    /// If not, the GUI asks the user to register the engine.
+    /// Until then, the engine should work with limited features.
+    fn check_registration(&mut self) -> bool {
+        false
+    }
+
+    /// Verify the registration of the user with the given name and code.
+    ///
+    /// Returns `true` if the registration is valid, the engine should work with all features then.
+    fn register(&mut self, _name: &str, _code: &str) -> bool {
+        false
+    }
+
+    /// The user wants to register the engine later.
+    ///
+    /// The engine should work with limited features until then.
+    fn register_later(&mut self) {}
+
+    /// The GUI turned the debug mode on or off.
+    ///
+    /// In debug mode, the engine can send additional information with
+    /// [`uci_debug!`](crate::uci_debug).
+    fn debug(&mut self, _enabled: bool) {}
+
+    /// The registry of the engine's options, if it uses one.
+    ///
+    /// The options of the registry are sent in addition to [`UciEngine::options`].
+    /// Values for these options are validated before [`UciEngine::set_option`] is called,
+    /// invalid values are reported to the GUI instead.
+    fn option_registry(&mut self) -> Option<&mut OptionRegistry> {
+        None
+    }
+
+    /// The GUI changed the value of the option with the given name.
+    ///
+    /// The value is `None` if the option is a button.
+    fn set_option(&mut self, _name: &str, _value: Option<&str>) {}
+
+    /// The next search will be from a different game.
+    fn new_game(&mut self) {}
+
+    /// Set up the given position on the internal board.
+    fn position(&mut self, position: &PositionCommand);
+
+    /// Search the current position and return the best move.
+    ///
+    /// The context can be used to send information about the search to the GUI.
+    fn go(&mut self, go: &GoCommand, search: &SearchContext) -> BestMoveCommand;
+
+    /// Any legal move in the current position, sent as `bestmove` if the search panics.
+    ///
+    /// This is only used if no best line has been sent during the search.
+    fn fallback_move(&mut self) -> Option<Move> {
+        None
+    }
+
+    /// The static evaluation of the current position, shown by the non-standard command `eval`.
+    fn evaluate(&mut self) -> Option<Evaluation> {
+        None
+    }
+
+    /// Handle a line that is neither a UCI command nor a built-in non-standard command.
+    ///
+    /// The answer can be sent with [`UciWriter::send_line`].
+    /// Returns `false` if the command is unknown, it is ignored then.
+    /// Commands received during a search are always ignored.
+    fn custom_command(&mut self, _command: &str, _writer: &UciWriter) -> bool {
+        false
+    }
+
+    /// The GUI wants the engine to stop searching.
+    ///
+    /// When searching with [`run_threaded`](super::run_threaded) or [`run_polled`](super::run_polled),
+    /// this is signaled by [`SearchContext::should_stop`] instead.
+    fn stop(&mut self) {}
+
+    /// The opponent played the move that the engine was pondering on.
+    ///
+    /// When searching with [`run_threaded`](super::run_threaded) or [`run_polled`](super::run_polled),
+    /// this is signaled by [`SearchContext::is_ponder_hit`] instead.
+    fn ponder_hit(&mut self) {}
+}
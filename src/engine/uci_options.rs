@@ -0,0 +1,189 @@
+use std::fmt::Display;
+
+use crate::{
+    chess::Variant,
+    command::engine_to_gui::{
+        CheckOption, ComboOption, EngineOption, OptionType, SpinOption, StringOption,
+    },
+};
+
+use super::{OptionError, OptionValue};
+
+/// Settings that can be changed by the GUI as engine options.
+///
+/// This is usually implemented with `#[derive(UciOptions)]`, which requires the `derive` feature:
+///
+/// ```ignore
+/// #[derive(Default, UciOptions)]
+/// struct Settings {
+///     #[uci(name = "Hash", min = 1, max = 1024, default = 16)]
+///     hash_mb: u32,
+///
+///     #[uci(default = true)]
+///     ponder: bool,
+///
+///     #[uci(name = "UCI_Variant", default = Variant::Standard)]
+///     variant: Variant,
+///
+///     // Sent as "Book File", the default is taken from `Settings::default()`
+///     book_file: String,
+/// }
+/// ```
+///
+/// Fields can be `bool` (check), integers (spin), `String` (string)
+/// or any type implementing [`ComboValue`] (combo).
+/// For enums without fields, `ComboValue` can be derived too.
+pub trait UciOptions {
+    /// The descriptions of the options, to send them to the GUI.
+    fn options() -> Vec<EngineOption>;
+
+    /// Validate the value sent by the GUI and apply it to the settings.
+    ///
+    /// The value is not applied if it's invalid.
+    fn set_option(&mut self, name: &str, value: Option<&str>) -> Result<(), OptionError>;
+}
+
+/// A type that can be used as a field of [`UciOptions`].
+pub trait OptionField: Sized {
+    /// The type of the option with the given default value.
+    ///
+    /// The `min` and `max` are only used by spin options
+    /// and default to the range of the type.
+    fn option_type(default: &Self, min: Option<i64>, max: Option<i64>) -> OptionType;
+
+    /// Convert a validated value of the option back to the field type.
+    fn from_value(value: OptionValue) -> Option<Self>;
+}
+
+/// A type with a fixed set of values, that is shown as a combo box.
+///
+/// The values are shown as formatted by [`Display`].
+/// For enums without fields, this can be derived with `#[derive(ComboValue)]`,
+/// which requires the `derive` feature.
+pub trait ComboValue: Sized + Display {
+    /// All possible values, in the order they are shown to the user.
+    fn values() -> Vec<Self>;
+}
+
+impl ComboValue for Variant {
+    fn values() -> Vec<Self> {
+        Variant::ALL.to_vec()
+    }
+}
+
+impl<T> OptionField for T
+where
+    T: ComboValue,
+{
+    fn option_type(default: &Self, _min: Option<i64>, _max: Option<i64>) -> OptionType {
+        OptionType::Combo(ComboOption::new(
+            T::values().iter().map(ToString::to_string),
+            default.to_string(),
+        ))
+    }
+
+    fn from_value(value: OptionValue) -> Option<Self> {
+        let OptionValue::Combo(value) = value else {
+            return None;
+        };
+
+        T::values()
+            .into_iter()
+            .find(|option| option.to_string() == value)
+    }
+}
+
+impl OptionField for bool {
+    fn option_type(default: &Self, _min: Option<i64>, _max: Option<i64>) -> OptionType {
+        OptionType::Check(CheckOption { default: *default })
+    }
+
+    fn from_value(value: OptionValue) -> Option<Self> {
+        match value {
+            OptionValue::Check(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl OptionField for String {
+    fn option_type(default: &Self, _min: Option<i64>, _max: Option<i64>) -> OptionType {
+        OptionType::String(StringOption {
+            default: default.clone(),
+        })
+    }
+
+    fn from_value(value: OptionValue) -> Option<Self> {
+        match value {
+            OptionValue::String(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+/// Implement [`OptionField`] for integer types as spin options.
+macro_rules! impl_spin_option_field {
+    ($($int:ty),*) => {
+        $(
+            impl OptionField for $int {
+                /// # Panics
+                ///
+                /// This function panics if the default is not between `min` and `max`.
+                fn option_type(default: &Self, min: Option<i64>, max: Option<i64>) -> OptionType {
+                    let type_min = i64::try_from(<$int>::MIN).unwrap_or(i64::MIN);
+                    let type_max = i64::try_from(<$int>::MAX).unwrap_or(i64::MAX);
+                    let default = i64::try_from(*default).unwrap_or(i64::MAX);
+
+                    OptionType::Spin(SpinOption::new(
+                        min.map_or(type_min, |min| min.max(type_min)),
+                        max.map_or(type_max, |max| max.min(type_max)),
+                        default,
+                    ))
+                }
+
+                fn from_value(value: OptionValue) -> Option<Self> {
+                    match value {
+                        OptionValue::Spin(value) => <$int>::try_from(value).ok(),
+                        _ => None,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_spin_option_field!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spin_range_is_limited_by_type() {
+        assert_eq!(
+            u8::option_type(&3, Some(-5), None),
+            OptionType::Spin(SpinOption::new(0, 255, 3))
+        );
+        assert_eq!(
+            u64::option_type(&3, None, None),
+            OptionType::Spin(SpinOption::new(0, i64::MAX, 3))
+        );
+    }
+
+    #[test]
+    fn variant_is_combo() {
+        let option = EngineOption::new(
+            "UCI_Variant",
+            Variant::option_type(&Variant::Standard, None, None),
+        );
+
+        assert_eq!(
+            option,
+            EngineOption::variant(&Variant::ALL, Variant::Standard)
+        );
+        assert_eq!(
+            Variant::from_value(OptionValue::Combo("atomic".to_string())),
+            Some(Variant::Atomic)
+        );
+    }
+}
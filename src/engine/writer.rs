@@ -0,0 +1,201 @@
+use std::{
+    fs::File,
+    io::{self, Write},
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, PoisonError,
+    },
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::command::engine_to_gui::{EngineToGuiCommand, InfoCommand};
+
+/// Sends commands from the engine to the GUI.
+///
+/// The writer can be cloned cheaply to send commands from multiple threads.
+/// Every command is written as a whole line and flushed immediately.
+#[derive(Clone)]
+pub struct UciWriter {
+    /// The output that the commands are written to, usually stdout.
+    output: Arc<Mutex<Box<dyn Write + Send>>>,
+
+    /// Set when the GUI has turned on the debug mode.
+    debug: Arc<AtomicBool>,
+
+    /// The file that all received and sent lines are logged to, see [`EngineOption::debug_log_file`].
+    ///
+    /// [`EngineOption::debug_log_file`]: crate::command::engine_to_gui::EngineOption::debug_log_file
+    transcript: Arc<Mutex<Option<File>>>,
+}
+
+impl UciWriter {
+    /// Create a new writer sending the commands to the given output.
+    pub fn new<W>(output: W) -> Self
+    where
+        W: Write + Send + 'static,
+    {
+        Self {
+            output: Arc::new(Mutex::new(Box::new(output))),
+            debug: Arc::new(AtomicBool::new(false)),
+            transcript: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Send a command to the GUI.
+    pub fn send(&self, command: &EngineToGuiCommand) -> io::Result<()> {
+        self.send_line(&command.to_string())
+    }
+
+    /// Send an info command to the GUI.
+    ///
+    /// In debug builds, the command is validated first
+    /// and a warning is sent if it doesn't conform to the UCI protocol.
+    pub fn send_info(&self, info: InfoCommand) -> io::Result<()> {
+        #[cfg(debug_assertions)]
+        if let Err(violations) = info.validate() {
+            let violations: Vec<_> = violations.iter().map(ToString::to_string).collect();
+            self.send(&EngineToGuiCommand::Info(Box::new(
+                InfoCommand::new()
+                    .with_string(format!("invalid info command: {}", violations.join(", "))),
+            )))?;
+        }
+
+        self.send(&EngineToGuiCommand::Info(Box::new(info)))
+    }
+
+    /// Send any text to the GUI, as `info string`.
+    pub fn send_string<S>(&self, string: S) -> io::Result<()>
+    where
+        S: Into<String>,
+    {
+        self.send_info(InfoCommand::new().with_string(string))
+    }
+
+    /// Determines if the GUI has turned on the debug mode with `debug on`.
+    ///
+    /// Use [`uci_debug!`](crate::uci_debug) to send debug information only in debug mode.
+    pub fn is_debug(&self) -> bool {
+        self.debug.load(Ordering::Relaxed)
+    }
+
+    /// Turn the debug mode on or off, for all clones of this writer.
+    pub(crate) fn set_debug(&self, debug: bool) {
+        self.debug.store(debug, Ordering::Relaxed);
+    }
+
+    /// Send a line that is not a UCI command, e.g. the answer to a non-standard command.
+    ///
+    /// GUIs don't understand these lines, so they should only be sent to a user in a terminal.
+    pub fn send_line(&self, line: &str) -> io::Result<()> {
+        // A panic while writing doesn't leave the output in an invalid state
+        let mut output = self.output.lock().unwrap_or_else(PoisonError::into_inner);
+
+        writeln!(output, "{line}")?;
+        output.flush()?;
+
+        // Log while holding the lock, so that the lines are logged in the order they are sent
+        self.log("out", line);
+        Ok(())
+    }
+
+    /// Log all received and sent lines to the given file, or close the current log file.
+    pub(crate) fn set_transcript_file(&self, file: Option<File>) {
+        *self
+            .transcript
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = file;
+    }
+
+    /// Log a line received from the GUI.
+    pub(crate) fn log_received(&self, line: &str) {
+        self.log("in", line);
+    }
+
+    /// Log a line with the current time and the direction, `in` or `out`.
+    ///
+    /// Errors are ignored, the log must not stop the communication with the GUI.
+    fn log(&self, direction: &str, line: &str) {
+        let mut transcript = self
+            .transcript
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        if let Some(file) = transcript.as_mut() {
+            let time = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default();
+            writeln!(
+                file,
+                "{}.{:03} {direction:<3} {line}",
+                time.as_secs(),
+                time.subsec_millis()
+            )
+            .ok();
+        }
+    }
+}
+
+/// Open the log file at the given path for appending, or return `None` if the path is empty.
+pub(crate) fn open_transcript(path: &Path) -> io::Result<Option<File>> {
+    if path.as_os_str().is_empty() {
+        return Ok(None);
+    }
+
+    File::options()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::test_utils::SharedBuffer;
+    use std::fs;
+
+    #[test]
+    #[cfg(debug_assertions)]
+    fn warn_about_invalid_info_in_debug_builds() {
+        let output = SharedBuffer::default();
+        let writer = UciWriter::new(output.clone());
+
+        writer
+            .send_info(InfoCommand::new().with_hash_full_permill(1200))
+            .unwrap();
+
+        assert_eq!(
+            output.lines(),
+            [
+                "info string invalid info command: hashfull 1200 is above 1000 permill",
+                "info hashfull 1000"
+            ]
+        );
+    }
+
+    #[test]
+    fn log_lines_to_transcript() {
+        let path =
+            std::env::temp_dir().join(format!("uci-transcript-{}-writer.log", std::process::id()));
+        fs::remove_file(&path).ok();
+
+        let writer = UciWriter::new(io::sink());
+        writer.log_received("ignored");
+        writer.set_transcript_file(open_transcript(&path).unwrap());
+        writer.log_received("isready");
+        writer.send(&EngineToGuiCommand::ReadyOk).unwrap();
+        writer.set_transcript_file(open_transcript(Path::new("")).unwrap());
+        writer.send(&EngineToGuiCommand::ReadyOk).unwrap();
+
+        let transcript = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        // Skip the timestamps
+        let lines: Vec<_> = transcript
+            .lines()
+            .map(|line| line.split_once(' ').unwrap().1)
+            .collect();
+        assert_eq!(lines, ["in  isready", "out readyok"]);
+    }
+}